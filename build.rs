@@ -0,0 +1,97 @@
+//! Generates one test per sample input and part, see `src/samples.rs`.
+//!
+//! Every `src/input/sampleNN[suffix].txt` (or `src/input/YYYY/sampleNN[suffix].txt` for
+//! years other than 2024) with a sibling `.toml` holding its expected answers, in the
+//! same format as `answers.toml`, gets a test for each answer. Only answers the puzzle
+//! states belong there: a part it gives no answer for is left out rather than filled in
+//! with whatever the solution currently prints.
+
+use std::{
+    env,
+    fmt::Write,
+    fs,
+    path::{Path, PathBuf},
+};
+
+const SAMPLE_DIR: &str = "src/input";
+/// Same as `solution::DEFAULT_YEAR`, whose samples sit directly in `SAMPLE_DIR`.
+const DEFAULT_YEAR: u16 = 2024;
+
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rerun-if-changed={}", SAMPLE_DIR);
+
+    let mut sample_dirs = vec![(DEFAULT_YEAR, PathBuf::from(SAMPLE_DIR))];
+    for entry in fs::read_dir(SAMPLE_DIR).expect("Sample directory should exist") {
+        let path = entry.expect("Sample directory should be readable").path();
+        let year = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .filter(|name| name.len() == 4)
+            .and_then(|name| name.parse::<u16>().ok());
+        if let (Some(year), true) = (year, path.is_dir()) {
+            println!("cargo:rerun-if-changed={}", path.display());
+            sample_dirs.push((year, path));
+        }
+    }
+    sample_dirs.sort();
+
+    let mut tests = String::new();
+    for (year, dir) in sample_dirs {
+        for sample in samples(&dir) {
+            write_tests(&mut tests, year, &dir, &sample);
+        }
+    }
+
+    let out_dir = env::var("OUT_DIR").expect("OUT_DIR is set by cargo");
+    fs::write(Path::new(&out_dir).join("sample_tests.rs"), tests)
+        .expect("Failed to write generated sample tests");
+}
+
+fn samples(dir: &Path) -> Vec<String> {
+    let mut samples: Vec<String> = fs::read_dir(dir)
+        .expect("Sample directory should exist")
+        .filter_map(|entry| {
+            let name = entry.ok()?.file_name().into_string().ok()?;
+            let stem = name.strip_suffix(".txt")?;
+            stem.starts_with("sample").then(|| stem.to_string())
+        })
+        .collect();
+    samples.sort();
+    samples
+}
+
+fn write_tests(tests: &mut String, year: u16, dir: &Path, sample: &str) {
+    let answers_path = dir.join(format!("{}.toml", sample));
+    let Ok(answers) = fs::read_to_string(&answers_path) else {
+        return;
+    };
+    let day: u8 = sample
+        .get(6..8)
+        .and_then(|day| day.parse().ok())
+        .unwrap_or_else(|| panic!("{} should be named sampleNN[suffix].txt", sample));
+    let answers: toml::Table = answers
+        .parse()
+        .unwrap_or_else(|e| panic!("Invalid {}: {}", answers_path.display(), e));
+    let Some(parts) = answers
+        .get(&format!("day{:02}", day))
+        .and_then(|parts| parts.as_table())
+    else {
+        panic!("{} has no [day{:02}] table", answers_path.display(), day);
+    };
+
+    let prefix = if year == DEFAULT_YEAR {
+        String::new()
+    } else {
+        format!("y{}_", year)
+    };
+    for part in [1, 2] {
+        if parts.contains_key(&format!("part{}", part)) {
+            writeln!(
+                tests,
+                "#[test]\nfn {prefix}{sample}_part{part}() {{\n    check({year}, {day}, \"{sample}\", {part});\n}}\n"
+            )
+            .unwrap();
+        }
+    }
+}
@@ -0,0 +1,307 @@
+//! Step-by-step recordings of the simulation days, played in the terminal or saved to disk.
+
+use std::{
+    collections::HashMap,
+    fs::{self, File},
+    io::{self, BufWriter, Write},
+    path::{Path, PathBuf},
+    thread,
+    time::{Duration, Instant},
+};
+
+use anyhow::{anyhow, Context, Result};
+
+use crate::{
+    gif::{self, GifEncoder},
+    render::{Cell, Color, Renderer},
+    vector_map::VectorMap,
+};
+
+/// One drawn state of a simulation.
+pub type Frame = VectorMap<Cell>;
+
+/// A simulation that can be watched one step at a time.
+pub trait Animate {
+    /// The current state.
+    fn frame(&self) -> Frame;
+
+    /// Advances by one step, returning `false` once there is nothing left to do.
+    fn step(&mut self) -> bool;
+}
+
+/// Where recorded frames go.
+pub trait FrameSink {
+    fn frame(&mut self, step: usize, frame: &Frame) -> Result<()>;
+
+    fn finish(&mut self) -> Result<()> {
+        Ok(())
+    }
+}
+
+/// Feeds the starting state of `animation` and then every step after it to `sink`, returning
+/// the number of steps taken.
+pub fn record(animation: &mut dyn Animate, sink: &mut dyn FrameSink) -> Result<usize> {
+    let mut steps = 0;
+    sink.frame(steps, &animation.frame())?;
+    while animation.step() {
+        steps += 1;
+        sink.frame(steps, &animation.frame())?;
+    }
+    sink.finish()?;
+    Ok(steps)
+}
+
+/// The sink for `--animate`: playback in the terminal, or a recording at `path` if one is given.
+pub fn sink(path: Option<&Path>, fps: u16) -> Result<Box<dyn FrameSink>> {
+    let delay = Duration::from_secs(1) / fps.max(1) as u32;
+    Ok(match path {
+        None => Box::new(Terminal::new(delay)),
+        Some(path) if path.extension().is_some_and(|extension| extension == "gif") => {
+            Box::new(GifFrames::create(path, delay)?)
+        }
+        Some(path) => Box::new(TextFrames::new(path)),
+    })
+}
+
+/// Redraws each frame over the last one in the terminal, `delay` apart.
+pub struct Terminal {
+    delay: Duration,
+    next: Option<Instant>,
+}
+
+impl Terminal {
+    pub fn new(delay: Duration) -> Self {
+        Terminal { delay, next: None }
+    }
+}
+
+impl FrameSink for Terminal {
+    fn frame(&mut self, step: usize, frame: &Frame) -> Result<()> {
+        if let Some(next) = self.next {
+            thread::sleep(next.saturating_duration_since(Instant::now()));
+        }
+        self.next = Some(Instant::now() + self.delay);
+
+        let picture = Renderer::new(frame, |&cell| cell).ansi();
+        let mut out = io::stdout().lock();
+        writeln!(out, "\x1b[H\x1b[2J{}step {}", picture, step)?;
+        out.flush()?;
+        Ok(())
+    }
+}
+
+/// Writes each frame as plain text to a file of its own, `step_00000.txt` onwards, in `dir`.
+pub struct TextFrames {
+    dir: PathBuf,
+}
+
+impl TextFrames {
+    pub fn new(dir: &Path) -> Self {
+        TextFrames {
+            dir: dir.to_path_buf(),
+        }
+    }
+}
+
+impl FrameSink for TextFrames {
+    fn frame(&mut self, step: usize, frame: &Frame) -> Result<()> {
+        if step == 0 {
+            fs::create_dir_all(&self.dir)
+                .with_context(|| format!("Failed to create {}", self.dir.display()))?;
+        }
+        let path = self.dir.join(format!("step_{:05}.txt", step));
+        let text = Renderer::new(frame, |&cell| cell).text();
+        fs::write(&path, text).with_context(|| format!("Failed to write {}", path.display()))
+    }
+}
+
+/// Largest size in pixels a frame is scaled up to.
+const GIF_TARGET_SIZE: i64 = 512;
+
+/// Streams the frames into an animated GIF, each cell a square of its color.
+pub struct GifFrames<W: Write> {
+    writer: Option<W>,
+    encoder: Option<GifEncoder<W>>,
+    /// In hundredths of a second, as GIFs count time.
+    delay: u16,
+    scale: usize,
+    palette: HashMap<Option<Color>, u8>,
+}
+
+impl GifFrames<BufWriter<File>> {
+    pub fn create(path: &Path, delay: Duration) -> Result<Self> {
+        let file =
+            File::create(path).with_context(|| format!("Failed to create {}", path.display()))?;
+        Ok(GifFrames::new(BufWriter::new(file), delay))
+    }
+}
+
+impl<W: Write> GifFrames<W> {
+    pub fn new(writer: W, delay: Duration) -> Self {
+        GifFrames {
+            writer: Some(writer),
+            encoder: None,
+            delay: (delay.as_millis() / 10).clamp(1, u16::MAX as u128) as u16,
+            scale: 1,
+            palette: HashMap::new(),
+        }
+    }
+
+    /// The finished GIF's writer, once [`FrameSink::finish`] has been called.
+    pub fn into_inner(self) -> Option<W> {
+        self.writer
+    }
+
+    fn pixels(&mut self, frame: &Frame) -> Vec<u8> {
+        let scale = self.scale;
+        let mut pixels =
+            Vec::with_capacity((frame.size().x * frame.size().y) as usize * scale * scale);
+        let palette = &mut self.palette;
+        let mut index = |color: Option<Color>| {
+            *palette
+                .entry(color)
+                .or_insert_with(|| gif::palette_index(color.unwrap_or(Color::BLACK)))
+        };
+
+        for row in frame.rows() {
+            let mut line = Vec::with_capacity(row.len() * scale);
+            for cell in row {
+                line.extend(std::iter::repeat_n(index(cell.color), scale));
+            }
+            for _ in 0..scale {
+                pixels.extend_from_slice(&line);
+            }
+        }
+        pixels
+    }
+}
+
+impl<W: Write> FrameSink for GifFrames<W> {
+    fn frame(&mut self, _step: usize, frame: &Frame) -> Result<()> {
+        if self.encoder.is_none() {
+            let size = frame.size();
+            self.scale = (GIF_TARGET_SIZE / size.x.max(size.y).max(1)).max(1) as usize;
+            let writer = self
+                .writer
+                .take()
+                .ok_or(anyhow!("The GIF is already finished"))?;
+            self.encoder = Some(GifEncoder::new(
+                writer,
+                size.x as usize * self.scale,
+                size.y as usize * self.scale,
+            )?);
+        }
+
+        let pixels = self.pixels(frame);
+        let delay = self.delay;
+        self.encoder.as_mut().unwrap().add_frame(&pixels, delay)
+    }
+
+    fn finish(&mut self) -> Result<()> {
+        let encoder = self
+            .encoder
+            .take()
+            .ok_or(anyhow!("No frames were recorded"))?;
+        self.writer = Some(encoder.finish()?);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vector::Vec2;
+
+    /// A dot moving right along a row.
+    struct Dot {
+        position: i64,
+        width: i64,
+    }
+
+    impl Animate for Dot {
+        fn frame(&self) -> Frame {
+            let mut frame = VectorMap::new(Vec2::new(self.width, 1), Cell::new('.'));
+            frame.set(&Vec2::new(self.position, 0), Cell::colored('o', Color::RED));
+            frame
+        }
+
+        fn step(&mut self) -> bool {
+            if self.position + 1 == self.width {
+                return false;
+            }
+            self.position += 1;
+            true
+        }
+    }
+
+    #[derive(Default)]
+    struct Collect(Vec<String>);
+
+    impl FrameSink for Collect {
+        fn frame(&mut self, _step: usize, frame: &Frame) -> Result<()> {
+            self.0.push(Renderer::new(frame, |&cell| cell).text());
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn records_every_step() {
+        let mut sink = Collect::default();
+        let steps = record(
+            &mut Dot {
+                position: 0,
+                width: 3,
+            },
+            &mut sink,
+        )
+        .unwrap();
+        assert_eq!(steps, 2);
+        assert_eq!(sink.0, vec!["o..\n", ".o.\n", "..o\n"]);
+    }
+
+    #[test]
+    fn records_gifs() {
+        let mut sink = GifFrames::new(Vec::new(), Duration::from_millis(50));
+        record(
+            &mut Dot {
+                position: 0,
+                width: 4,
+            },
+            &mut sink,
+        )
+        .unwrap();
+        let gif = sink.into_inner().unwrap();
+
+        // Scaled up to 512×128, with a frame for each of the four positions.
+        assert_eq!(&gif[..10], b"GIF89a\x00\x02\x80\x00");
+        let frames = gif
+            .windows(3)
+            .filter(|window| window == &[0x21, 0xf9, 0x04]);
+        assert_eq!(frames.count(), 4);
+        assert_eq!(gif.last(), Some(&0x3b));
+    }
+
+    #[test]
+    fn records_text_frames() {
+        let dir = std::env::temp_dir().join(format!("aoc-frames-{}", std::process::id()));
+        let mut sink = TextFrames::new(&dir);
+        record(
+            &mut Dot {
+                position: 0,
+                width: 2,
+            },
+            &mut sink,
+        )
+        .unwrap();
+
+        assert_eq!(
+            fs::read_to_string(dir.join("step_00000.txt")).unwrap(),
+            "o.\n"
+        );
+        assert_eq!(
+            fs::read_to_string(dir.join("step_00001.txt")).unwrap(),
+            ".o\n"
+        );
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,230 @@
+use std::{
+    collections::BTreeMap,
+    fmt::{Display, Formatter},
+    fs,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+use anyhow::{anyhow, Context, Result};
+
+use crate::{input, solution::DayResult};
+
+const ANSWERS_FILE: &str = "answers.toml";
+
+/// Known-correct answers, keyed by day and part.
+///
+/// ```toml
+/// [day01]
+/// part1 = 11
+/// part2 = "31"
+/// ```
+#[derive(Debug, Default, PartialEq)]
+pub struct Answers(BTreeMap<(u8, u8), String>);
+
+impl Answers {
+    pub fn default_path(year: u16) -> PathBuf {
+        input::year_path(&input::input_dir(), year, ANSWERS_FILE)
+    }
+
+    pub fn load(path: &Path) -> Result<Self> {
+        fs::read_to_string(path)
+            .with_context(|| format!("Failed to read answers from {}", path.display()))?
+            .parse()
+            .with_context(|| format!("Invalid answers file {}", path.display()))
+    }
+
+    pub fn expected(&self, day: u8, part: u8) -> Option<&str> {
+        self.0.get(&(day, part)).map(String::as_str)
+    }
+
+    pub fn verify(&self, results: &[DayResult]) -> Verification {
+        let mut verification = Verification::default();
+        for result in results {
+            for (part, part_result) in [(1, &result.part1), (2, &result.part2)] {
+                let Some(part_result) = part_result else {
+                    continue;
+                };
+                match self.expected(result.day, part) {
+                    Some(expected) if expected == part_result.answer => verification.matched += 1,
+                    Some(expected) => verification.mismatches.push(Mismatch {
+                        day: result.day,
+                        part,
+                        expected: expected.to_string(),
+                        actual: part_result.answer.clone(),
+                    }),
+                    None => verification.unknown.push((result.day, part)),
+                }
+            }
+        }
+        verification
+    }
+}
+
+impl FromStr for Answers {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let table: toml::Table = s.parse()?;
+        let mut answers = BTreeMap::new();
+
+        for (day_key, parts) in table {
+            let day = day_key
+                .strip_prefix("day")
+                .and_then(|day| day.parse::<u8>().ok())
+                .ok_or(anyhow!(
+                    "Invalid day key: {}, expected e.g. [day01]",
+                    day_key
+                ))?;
+            let parts = parts
+                .as_table()
+                .ok_or(anyhow!("[{}] must be a table of part answers", day_key))?;
+
+            for (part_key, answer) in parts {
+                let part = match part_key.as_str() {
+                    "part1" => 1,
+                    "part2" => 2,
+                    _ => return Err(anyhow!("Invalid part key: {}.{}", day_key, part_key)),
+                };
+                let answer = match answer {
+                    toml::Value::String(answer) => answer.clone(),
+                    toml::Value::Integer(answer) => answer.to_string(),
+                    toml::Value::Float(answer) => answer.to_string(),
+                    _ => return Err(anyhow!("Invalid answer for {}.{}", day_key, part_key)),
+                };
+                answers.insert((day, part), answer);
+            }
+        }
+
+        Ok(Answers(answers))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mismatch {
+    pub day: u8,
+    pub part: u8,
+    pub expected: String,
+    pub actual: String,
+}
+
+impl Display for Mismatch {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Day {:02} part {}:", self.day, self.part)?;
+        writeln!(f, "  - expected: {}", self.expected)?;
+        write!(f, "  + actual:   {}", self.actual)
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct Verification {
+    pub matched: usize,
+    pub mismatches: Vec<Mismatch>,
+    pub unknown: Vec<(u8, u8)>,
+}
+
+impl Verification {
+    pub fn is_ok(&self) -> bool {
+        self.mismatches.is_empty()
+    }
+}
+
+impl Display for Verification {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for mismatch in &self.mismatches {
+            writeln!(f, "{}", mismatch)?;
+        }
+        for (day, part) in &self.unknown {
+            writeln!(f, "Day {:02} part {}: no known answer", day, part)?;
+        }
+        write!(
+            f,
+            "Verified {} answer(s): {} matched, {} mismatched, {} unknown",
+            self.matched + self.mismatches.len(),
+            self.matched,
+            self.mismatches.len(),
+            self.unknown.len()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::solution::PartResult;
+
+    fn part(answer: &str) -> Option<PartResult> {
+        Some(PartResult {
+            answer: answer.to_string(),
+            duration: Duration::ZERO,
+            memory: None,
+        })
+    }
+
+    #[test]
+    fn parses_answers() {
+        let answers: Answers = r#"
+[day01]
+part1 = 11
+part2 = "31"
+
+[day13]
+part1 = 480.0
+"#
+        .parse()
+        .unwrap();
+
+        assert_eq!(answers.expected(1, 1), Some("11"));
+        assert_eq!(answers.expected(1, 2), Some("31"));
+        assert_eq!(answers.expected(13, 1), Some("480"));
+        assert_eq!(answers.expected(13, 2), None);
+    }
+
+    #[test]
+    fn rejects_unknown_keys() {
+        assert!("[first]\npart1 = 1".parse::<Answers>().is_err());
+        assert!("[day01]\npart3 = 1".parse::<Answers>().is_err());
+        assert!("[day01]\npart1 = true".parse::<Answers>().is_err());
+    }
+
+    #[test]
+    fn verifies_results() {
+        let answers: Answers = "[day06]\npart1 = 41\npart2 = 6".parse().unwrap();
+        let results = [
+            DayResult {
+                year: 2024,
+                day: 6,
+                input_bytes: 0,
+                parse_duration: None,
+                part1: part("41"),
+                part2: part("7"),
+                failures: Vec::new(),
+            },
+            DayResult {
+                year: 2024,
+                day: 7,
+                input_bytes: 0,
+                parse_duration: None,
+                part1: part("3749"),
+                part2: None,
+                failures: Vec::new(),
+            },
+        ];
+
+        let verification = answers.verify(&results);
+        assert!(!verification.is_ok());
+        assert_eq!(verification.matched, 1);
+        assert_eq!(
+            verification.mismatches,
+            vec![Mismatch {
+                day: 6,
+                part: 2,
+                expected: "6".to_string(),
+                actual: "7".to_string(),
+            }]
+        );
+        assert_eq!(verification.unknown, vec![(7, 1)]);
+    }
+}
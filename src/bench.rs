@@ -0,0 +1,142 @@
+use std::time::{Duration, Instant};
+
+use anyhow::Result;
+
+const MAX_WARMUP_RUNS: usize = 3;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Stats {
+    pub runs: usize,
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub stddev: Duration,
+}
+
+impl Stats {
+    pub fn from_samples(samples: &[Duration]) -> Self {
+        assert!(!samples.is_empty(), "Cannot compute stats of no samples");
+        let mut sorted = samples.to_vec();
+        sorted.sort();
+
+        let runs = sorted.len();
+        let median = if runs.is_multiple_of(2) {
+            (sorted[runs / 2 - 1] + sorted[runs / 2]) / 2
+        } else {
+            sorted[runs / 2]
+        };
+
+        let mean_secs = sorted.iter().map(Duration::as_secs_f64).sum::<f64>() / runs as f64;
+        let variance = if runs > 1 {
+            sorted
+                .iter()
+                .map(|d| (d.as_secs_f64() - mean_secs).powi(2))
+                .sum::<f64>()
+                / (runs - 1) as f64
+        } else {
+            0.0
+        };
+
+        Stats {
+            runs,
+            min: sorted[0],
+            median,
+            mean: Duration::from_secs_f64(mean_secs),
+            stddev: Duration::from_secs_f64(variance.sqrt()),
+        }
+    }
+}
+
+/// Runs `f` a few times to warm caches up, then measures `runs` executions of it.
+pub fn measure<F>(runs: usize, mut f: F) -> Result<Stats>
+where
+    F: FnMut() -> Result<()>,
+{
+    for _ in 0..runs.min(MAX_WARMUP_RUNS) {
+        f()?;
+    }
+
+    let mut samples = Vec::with_capacity(runs);
+    for _ in 0..runs {
+        let start = Instant::now();
+        f()?;
+        samples.push(start.elapsed());
+    }
+
+    Ok(Stats::from_samples(&samples))
+}
+
+#[derive(Debug, Clone)]
+pub struct DayBench {
+    pub day: u8,
+    pub phases: Vec<(String, Stats)>,
+}
+
+pub fn print_bench(bench: &DayBench) {
+    print!("{}", render_bench(bench));
+}
+
+fn render_bench(bench: &DayBench) -> String {
+    let name_width = bench
+        .phases
+        .iter()
+        .map(|(name, _)| name.len())
+        .max()
+        .unwrap_or(0)
+        .max("Phase".len());
+
+    let mut out = format!("Day {:02}\n====================\n", bench.day);
+    out.push_str(&format!(
+        "{:<name_width$} | {:>5} | {:>10} | {:>10} | {:>10} | {:>10}\n",
+        "Phase", "Runs", "Min", "Median", "Mean", "Std dev"
+    ));
+    for (name, stats) in &bench.phases {
+        out.push_str(&format!(
+            "{:<name_width$} | {:>5} | {:>10} | {:>10} | {:>10} | {:>10}\n",
+            name,
+            stats.runs,
+            format!("{:.2?}", stats.min),
+            format!("{:.2?}", stats.median),
+            format!("{:.2?}", stats.mean),
+            format!("{:.2?}", stats.stddev),
+        ));
+    }
+    out.push('\n');
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn computes_stats() {
+        let samples = [4, 1, 3, 2].map(Duration::from_millis);
+        let stats = Stats::from_samples(&samples);
+        assert_eq!(stats.runs, 4);
+        assert_eq!(stats.min, Duration::from_millis(1));
+        assert_eq!(stats.median, Duration::from_micros(2500));
+        assert_eq!(stats.mean, Duration::from_micros(2500));
+        // sample standard deviation of 1, 2, 3, 4 ms
+        assert_eq!(stats.stddev.as_micros(), 1290);
+    }
+
+    #[test]
+    fn single_sample_has_no_deviation() {
+        let stats = Stats::from_samples(&[Duration::from_millis(7)]);
+        assert_eq!(stats.median, Duration::from_millis(7));
+        assert_eq!(stats.stddev, Duration::ZERO);
+    }
+
+    #[test]
+    fn measures_requested_runs_after_warmup() {
+        let mut calls = 0;
+        let stats = measure(5, || {
+            calls += 1;
+            Ok(())
+        })
+        .unwrap();
+        assert_eq!(stats.runs, 5);
+        assert_eq!(calls, 5 + MAX_WARMUP_RUNS);
+    }
+}
@@ -1,7 +1,228 @@
-use clap::Parser;
+use std::{ops::RangeInclusive, path::PathBuf, time::Duration};
+
+use anyhow::{anyhow, Result};
+use clap::{ArgGroup, Parser, Subcommand};
+
+use aoc2024::{
+    report::Format,
+    solution::{Parts, RunOptions, DEFAULT_YEAR},
+};
 
 #[derive(Parser)]
+#[command(group(ArgGroup::new("selection").required(true).args(["day", "all", "days", "list"])))]
+#[command(args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
 pub struct Cli {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+
+    #[arg(short, long)]
+    pub day: Option<u8>,
+
+    /// Which year's days to run
+    #[arg(short, long, default_value_t = DEFAULT_YEAR)]
+    pub year: u16,
+
+    /// Run every day in sequence
     #[arg(short, long)]
-    pub day: u8,
+    pub all: bool,
+
+    /// Run a range of days, e.g. `3..=12`, `3..12` or `3-12`
+    #[arg(long, value_parser = parse_day_range)]
+    pub days: Option<RangeInclusive<u8>>,
+
+    /// List the implemented days and their answer types
+    #[arg(short, long)]
+    pub list: bool,
+
+    /// Read the input from this file instead of the input directory, `-` for stdin
+    #[arg(short, long, conflicts_with_all = ["all", "days", "list"])]
+    pub input: Option<String>,
+
+    /// Which part to run: 1, 2 or both
+    #[arg(short, long, default_value = "both")]
+    pub part: Parts,
+
+    /// Benchmark each part over N runs (after a short warm-up) instead of solving once
+    #[arg(long, value_name = "N", value_parser = clap::value_parser!(u32).range(1..))]
+    pub bench: Option<u32>,
+
+    /// Run parallel solutions on N threads, 1 runs them sequentially (defaults to one per core)
+    #[arg(long, value_name = "N", value_parser = clap::value_parser!(u16).range(1..))]
+    pub threads: Option<u16>,
+
+    /// Solve each part over and over for this long, e.g. `10s`, so a sampling profiler can
+    /// attach, then summarize the spans the day recorded
+    #[arg(long, value_name = "BUDGET", value_parser = parse_duration, conflicts_with = "bench")]
+    pub profile: Option<Duration>,
+
+    /// Output format: text, json or csv
+    #[arg(short, long, default_value = "text", conflicts_with_all = ["bench", "profile"])]
+    pub format: Format,
+
+    /// Compare answers against the answers file and fail on any mismatch
+    #[arg(long, conflicts_with_all = ["bench", "profile"])]
+    pub verify: bool,
+
+    /// Answers file used by --verify, defaults to answers.toml in the input directory
+    #[arg(long, requires = "verify")]
+    pub answers: Option<PathBuf>,
+
+    /// Give up on a part after this long, e.g. `30s`, `500ms` or `2m` (plain numbers are seconds)
+    #[arg(short, long, value_parser = parse_duration, conflicts_with_all = ["bench", "profile"])]
+    pub timeout: Option<Duration>,
+
+    /// Play the day's simulation step by step in the terminal instead of printing the answers
+    #[arg(long, conflicts_with_all = ["all", "days", "list", "bench", "profile", "verify"])]
+    #[arg(conflicts_with_all = ["timeout", "format"])]
+    pub animate: bool,
+
+    /// Animation speed in steps per second
+    #[arg(long, value_name = "N", default_value_t = 20, requires = "animate")]
+    #[arg(value_parser = clap::value_parser!(u16).range(1..))]
+    pub fps: u16,
+
+    /// Record the animation instead of playing it: an animated image for a `.gif` path,
+    /// otherwise a directory of text frames
+    #[arg(long, value_name = "PATH", requires = "animate")]
+    pub record: Option<PathBuf>,
+}
+
+#[derive(Subcommand)]
+pub enum Command {
+    /// Download a day's input into the input directory, unless it is already there
+    ///
+    /// Needs `$AOC_SESSION` set to your adventofcode.com session cookie; `$AOC_BASE_URL`
+    /// points the download at another server.
+    Fetch {
+        #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+
+        #[arg(short, long, default_value_t = DEFAULT_YEAR)]
+        year: u16,
+    },
+    /// Run a day on its input and samples, and re-run whenever one of those files changes
+    ///
+    /// Only input files are watched: changes to the solution itself still need a rebuild.
+    Watch {
+        #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+
+        /// Watch this input file instead of the one in the input directory
+        #[arg(short, long)]
+        input: Option<PathBuf>,
+
+        #[arg(short, long, default_value_t = DEFAULT_YEAR)]
+        year: u16,
+
+        /// Which part to run: 1, 2 or both
+        #[arg(short, long, default_value = "both")]
+        part: Parts,
+    },
+    /// Generate a solution module for a new day, register it and add empty sample files
+    ///
+    /// Refuses to touch a day that already has a module.
+    New {
+        #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+
+        #[arg(short, long, default_value_t = DEFAULT_YEAR)]
+        year: u16,
+    },
+}
+
+impl Cli {
+    pub fn run_options(&self) -> RunOptions {
+        RunOptions {
+            parts: self.part,
+            timeout: self.timeout,
+        }
+    }
+
+    pub fn selected_days(&self) -> RangeInclusive<u8> {
+        match (self.day, &self.days) {
+            (Some(day), _) => day..=day,
+            (None, Some(days)) => days.clone(),
+            (None, None) => 1..=25,
+        }
+    }
+}
+
+fn parse_day_range(s: &str) -> Result<RangeInclusive<u8>> {
+    let (start, end) = if let Some((start, end)) = s.split_once("..=") {
+        (start.trim().parse()?, end.trim().parse()?)
+    } else if let Some((start, end)) = s.split_once("..") {
+        let end: u8 = end.trim().parse()?;
+        let end = end
+            .checked_sub(1)
+            .ok_or(anyhow!("Invalid day range: {}", s))?;
+        (start.trim().parse()?, end)
+    } else if let Some((start, end)) = s.split_once('-') {
+        (start.trim().parse()?, end.trim().parse()?)
+    } else {
+        let day = s.trim().parse()?;
+        (day, day)
+    };
+
+    if start > end {
+        return Err(anyhow!("Invalid day range: {}", s));
+    }
+    if start < 1 || end > 25 {
+        return Err(anyhow!("Invalid day range: {}, days go from 1 to 25", s));
+    }
+
+    Ok(start..=end)
+}
+
+fn parse_duration(s: &str) -> Result<Duration> {
+    let s = s.trim();
+    let split = s
+        .find(|c: char| !c.is_ascii_digit() && c != '.')
+        .unwrap_or(s.len());
+    let (value, unit) = s.split_at(split);
+    let value: f64 = value
+        .parse()
+        .map_err(|_| anyhow!("Invalid duration: {}", s))?;
+
+    let seconds = match unit {
+        "ms" => value / 1000.0,
+        "" | "s" => value,
+        "m" => value * 60.0,
+        _ => {
+            return Err(anyhow!(
+                "Invalid duration unit: {}, expected ms, s or m",
+                unit
+            ))
+        }
+    };
+
+    Duration::try_from_secs_f64(seconds).map_err(|err| anyhow!("Invalid duration: {}: {}", s, err))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn day_ranges() {
+        assert_eq!(parse_day_range("3..=12").unwrap(), 3..=12);
+        assert_eq!(parse_day_range("3..12").unwrap(), 3..=11);
+        assert_eq!(parse_day_range("3-12").unwrap(), 3..=12);
+        assert_eq!(parse_day_range("7").unwrap(), 7..=7);
+        assert!(parse_day_range("12..=3").is_err());
+        assert!(parse_day_range("a..b").is_err());
+        assert!(parse_day_range("0..=3").is_err());
+        assert!(parse_day_range("3..=26").is_err());
+        assert_eq!(parse_day_range("20..26").unwrap(), 20..=25);
+    }
+
+    #[test]
+    fn durations() {
+        assert_eq!(parse_duration("500ms").unwrap(), Duration::from_millis(500));
+        assert_eq!(parse_duration("30s").unwrap(), Duration::from_secs(30));
+        assert_eq!(parse_duration("1.5").unwrap(), Duration::from_millis(1500));
+        assert_eq!(parse_duration("2m").unwrap(), Duration::from_secs(120));
+        assert!(parse_duration("10h").is_err());
+        assert!(parse_duration("s").is_err());
+        assert!(parse_duration("99999999999999999999999m").is_err());
+    }
 }
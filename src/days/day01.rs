@@ -3,18 +3,23 @@ use std::collections::HashMap;
 use anyhow::Result;
 use itertools::process_results;
 
-use crate::solution::Solution;
+use crate::{parse::Text, solution::Solution};
 
 pub struct Day01;
 
 impl Solution for Day01 {
     type Answer = usize;
+    type Parsed<'a> = (Vec<usize>, Vec<usize>);
     fn day(&self) -> u8 {
         1
     }
 
-    fn part1(input: &str) -> Result<Self::Answer> {
-        let (mut left, mut right) = Day01::parse_input(input)?;
+    fn parse(input: &str) -> Result<Self::Parsed<'_>> {
+        Day01::parse_input(input)
+    }
+
+    fn part1((left, right): &Self::Parsed<'_>) -> Result<Self::Answer> {
+        let (mut left, mut right) = (left.clone(), right.clone());
 
         left.sort();
         right.sort();
@@ -24,9 +29,7 @@ impl Solution for Day01 {
         Ok(sorted_pairs.map(|(a, b)| a.abs_diff(*b)).sum())
     }
 
-    fn part2(input: &str) -> Result<Self::Answer> {
-        let (right, left) = Day01::parse_input(input)?;
-
+    fn part2((right, left): &Self::Parsed<'_>) -> Result<Self::Answer> {
         let mut frequency = HashMap::new();
         for n in left {
             *frequency.entry(n).or_insert(0) += 1;
@@ -45,11 +48,12 @@ impl Solution for Day01 {
 
 impl Day01 {
     fn parse_input(input: &str) -> Result<(Vec<usize>, Vec<usize>)> {
+        let text = Text::new(input);
         process_results(
-            input
-                .lines()
-                .map(|line| line.split_once(" ").unwrap())
-                .map(|(a, b)| Ok((a.trim().parse::<usize>()?, b.trim().parse::<usize>()?))),
+            text.lines().map(|line| {
+                let (a, b) = text.split_once(line, " ")?;
+                Ok((text.parse::<usize>(a)?, text.parse::<usize>(b)?))
+            }),
             |pairs| pairs.unzip(),
         )
     }
@@ -3,26 +3,29 @@ use std::str::FromStr;
 use anyhow::{Error, Ok, Result};
 use itertools::Itertools;
 
-use crate::solution::Solution;
+use crate::{parse::Text, solution::Solution};
 
 pub struct Day02;
 
 impl Solution for Day02 {
     type Answer = usize;
+    type Parsed<'a> = Vec<LevelSequence>;
     fn day(&self) -> u8 {
         2
     }
 
-    fn part1(input: &str) -> Result<Self::Answer> {
-        let parsed_input = Self::parse_input(input)?;
+    fn parse(input: &str) -> Result<Self::Parsed<'_>> {
+        Self::parse_input(input)
+    }
+
+    fn part1(parsed_input: &Vec<LevelSequence>) -> Result<Self::Answer> {
         let safe_sequences = parsed_input.iter().filter(|seq| seq.is_safe(None)).count();
         Ok(safe_sequences)
     }
 
-    fn part2(input: &str) -> Result<Self::Answer> {
-        let parsed_input = Self::parse_input(input)?;
+    fn part2(parsed_input: &Vec<LevelSequence>) -> Result<Self::Answer> {
         let safe_sequences = parsed_input
-            .into_iter()
+            .iter()
             .filter(|seq| seq.is_safe_with_tolerance())
             .count();
 
@@ -32,21 +35,22 @@ impl Solution for Day02 {
 
 impl Day02 {
     fn parse_input(input: &str) -> Result<Vec<LevelSequence>> {
-        input.lines().map(|line| line.parse()).collect()
+        Ok(Text::new(input).parse_lines()?)
     }
 }
 
 #[derive(Debug)]
-struct LevelSequence(Vec<u8>);
+pub struct LevelSequence(Vec<u8>);
 
 impl FromStr for LevelSequence {
     type Err = Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let text = Text::new(s);
         Ok(LevelSequence(
             s.split_ascii_whitespace()
-                .map(|s| s.parse().map_err(Error::from))
-                .collect::<Result<_>>()?,
+                .map(|level| text.parse(level))
+                .collect::<Result<_, _>>()?,
         ))
     }
 }
@@ -10,11 +10,16 @@ const FULL_PATTERN: &str = r"mul\((\d{1,3}),(\d{1,3})\)|do\(\)|don't\(\)";
 
 impl Solution for Day03 {
     type Answer = usize;
+    type Parsed<'a> = &'a str;
     fn day(&self) -> u8 {
         3
     }
 
-    fn part1(input: &str) -> Result<Self::Answer> {
+    fn parse(input: &str) -> Result<Self::Parsed<'_>> {
+        Ok(input)
+    }
+
+    fn part1(input: &&str) -> Result<Self::Answer> {
         let mul_pattern = Regex::new(MUL_PATTERN).unwrap();
 
         let mut result = 0;
@@ -29,7 +34,7 @@ impl Solution for Day03 {
         Ok(result)
     }
 
-    fn part2(input: &str) -> Result<Self::Answer> {
+    fn part2(input: &&str) -> Result<Self::Answer> {
         let pattern = Regex::new(FULL_PATTERN).unwrap();
         let mut result = 0;
         let mut active = true;
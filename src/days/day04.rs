@@ -2,19 +2,22 @@ use std::str::FromStr;
 
 use anyhow::Result;
 
-use crate::solution::Solution;
+use crate::{parse::Text, solution::Solution};
 
 pub struct Day04;
 
 impl Solution for Day04 {
     type Answer = usize;
+    type Parsed<'a> = WordSearchBoard;
     fn day(&self) -> u8 {
         4
     }
 
-    fn part1(input: &str) -> Result<Self::Answer> {
-        let board: WordSearchBoard = input.parse()?;
+    fn parse(input: &str) -> Result<Self::Parsed<'_>> {
+        input.parse()
+    }
 
+    fn part1(board: &WordSearchBoard) -> Result<Self::Answer> {
         let mut result = 0;
         for (x, y) in board.all_letters('X') {
             let options = board.find_xmas_options(x as isize, y as isize);
@@ -28,9 +31,7 @@ impl Solution for Day04 {
         Ok(result)
     }
 
-    fn part2(input: &str) -> Result<Self::Answer> {
-        let board: WordSearchBoard = input.parse()?;
-
+    fn part2(board: &WordSearchBoard) -> Result<Self::Answer> {
         let all_xmas = board
             .all_letters('A')
             .filter(|(x, y)| board.is_xmas(*x as isize, *y as isize))
@@ -40,7 +41,7 @@ impl Solution for Day04 {
     }
 }
 
-struct WordSearchBoard(Vec<Vec<char>>);
+pub struct WordSearchBoard(Vec<Vec<char>>);
 
 impl WordSearchBoard {
     fn find_xmas_options(&self, x: isize, y: isize) -> Vec<String> {
@@ -195,7 +196,11 @@ impl FromStr for WordSearchBoard {
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         Ok(WordSearchBoard(
-            s.lines().map(|line| line.chars().collect()).collect(),
+            Text::new(s)
+                .grid()?
+                .into_iter()
+                .map(|row| row.chars().collect())
+                .collect(),
         ))
     }
 }
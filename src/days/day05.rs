@@ -1,20 +1,24 @@
 use std::str::FromStr;
 
-use anyhow::{anyhow, Result};
+use anyhow::Result;
 use itertools::Itertools;
 
-use crate::solution::Solution;
+use crate::{parse::Text, solution::Solution};
 
 pub struct Day05;
 
 impl Solution for Day05 {
     type Answer = u32;
+    type Parsed<'a> = ManualInstructions;
     fn day(&self) -> u8 {
         5
     }
 
-    fn part1(input: &str) -> Result<Self::Answer> {
-        let manual = input.parse::<ManualInstructions>()?;
+    fn parse(input: &str) -> Result<Self::Parsed<'_>> {
+        input.parse()
+    }
+
+    fn part1(manual: &ManualInstructions) -> Result<Self::Answer> {
         let mut result = 0;
         for update in &manual.updates {
             if manual.page_matches_rules(update) {
@@ -25,8 +29,7 @@ impl Solution for Day05 {
         Ok(result)
     }
 
-    fn part2(input: &str) -> Result<Self::Answer> {
-        let manual = input.parse::<ManualInstructions>()?;
+    fn part2(manual: &ManualInstructions) -> Result<Self::Answer> {
         let mut result = 0;
         for update in &manual.updates {
             if !manual.page_matches_rules(update) {
@@ -41,7 +44,7 @@ impl Solution for Day05 {
 }
 
 #[derive(Debug)]
-struct ManualInstructions {
+pub struct ManualInstructions {
     rules: Vec<(u8, u8)>,
     updates: Vec<Vec<u8>>,
 }
@@ -50,29 +53,21 @@ impl FromStr for ManualInstructions {
     type Err = anyhow::Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (rule_str, updates_str) = s
-            .split_once("\n\n")
-            .ok_or(anyhow!("Failed to split input string"))?;
+        let text = Text::new(s);
+        let (rule_str, updates_str) = text.split_once(s, "\n\n")?;
 
         let rules = rule_str
             .lines()
             .map(|line| {
-                let (before, after) = line
-                    .split_once("|")
-                    .ok_or(anyhow!("Failed to split rule"))?;
-
-                Ok((before.parse()?, after.parse()?))
+                let (before, after) = text.split_once(line, "|")?;
+                Ok((text.parse(before)?, text.parse(after)?))
             })
             .collect::<Result<_>>()?;
 
         let updates: Vec<Vec<u8>> = updates_str
             .lines()
-            .map(|line| {
-                line.split(",")
-                    .map(|n| Ok(n.parse::<u8>()?))
-                    .collect::<Result<Vec<_>>>()
-            })
-            .collect::<Result<_>>()?;
+            .map(|line| line.split(',').map(|n| text.parse(n)).collect())
+            .collect::<Result<_, _>>()?;
 
         Ok(ManualInstructions { rules, updates })
     }
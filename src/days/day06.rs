@@ -3,29 +3,50 @@ use std::{collections::HashSet, str::FromStr};
 use anyhow::Result;
 use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
 
-use crate::{solution::Solution, vector::Vec2};
+use crate::{
+    animate::{Animate, Frame},
+    parse::Text,
+    render::{Cell, Color},
+    solution::Solution,
+    vector::Vec2,
+    vector_map::VectorMap,
+};
 
 pub struct Day06;
 
 impl Solution for Day06 {
     type Answer = usize;
+    type Parsed<'a> = Lab;
     fn day(&self) -> u8 {
         6
     }
 
-    fn part1(input: &str) -> Result<Self::Answer> {
-        let mut lab = input.parse::<Lab>()?;
+    fn parse(input: &str) -> Result<Self::Parsed<'_>> {
+        input.parse()
+    }
+
+    fn part1(lab: &Lab) -> Result<Self::Answer> {
+        let mut lab = lab.clone();
         Ok(lab.run().len())
     }
 
-    fn part2(input: &str) -> Result<Self::Answer> {
-        let lab = input.parse::<Lab>()?;
+    fn part2(lab: &Lab) -> Result<Self::Answer> {
         Ok(lab.find_loop_inducing_positions_parallel())
     }
+
+    fn animation<'a>(part: u8, lab: &'a Lab) -> Option<Box<dyn Animate + 'a>> {
+        match part {
+            1 => Some(Box::new(GuardWalk {
+                lab: lab.clone(),
+                visited: HashSet::new(),
+            })),
+            _ => None,
+        }
+    }
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
-struct Lab {
+pub struct Lab {
     map: HashSet<Vec2>,
     size: Vec2,
     guard_position: Vec2,
@@ -94,18 +115,61 @@ impl Lab {
     }
 }
 
+/// The guard's walk out of the lab, one step or turn at a time.
+pub struct GuardWalk {
+    lab: Lab,
+    visited: HashSet<Vec2>,
+}
+
+impl Animate for GuardWalk {
+    fn frame(&self) -> Frame {
+        let mut frame = VectorMap::new(self.lab.size, Cell::new('.'));
+        for obstacle in &self.lab.map {
+            frame.set(obstacle, Cell::colored('#', Color::GRAY));
+        }
+        for position in &self.visited {
+            frame.set(position, Cell::colored('X', Color::YELLOW));
+        }
+
+        let guard = match self.lab.guard_direction {
+            Vec2::UP => '^',
+            Vec2::RIGHT => '>',
+            Vec2::DOWN => 'v',
+            _ => '<',
+        };
+        frame.try_set(&self.lab.guard_position, Cell::colored(guard, Color::RED));
+        frame
+    }
+
+    fn step(&mut self) -> bool {
+        if !self
+            .lab
+            .guard_position
+            .contained_in(Vec2::new(0, 0), self.lab.size)
+        {
+            return false;
+        }
+
+        self.visited.insert(self.lab.guard_position);
+        self.lab.take_step();
+        true
+    }
+}
+
 impl FromStr for Lab {
     type Err = anyhow::Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let text = Text::new(s);
+        let rows = text.grid()?;
         let mut map = HashSet::new();
-        let mut guard_position = (0, 0).into();
+        let mut guard_position = None;
 
-        let y_size = s.lines().count();
-        let x_size = s.lines().next().unwrap().chars().count();
+        let y_size = rows.len();
+        let x_size = rows[0].chars().count();
         let size = (x_size, y_size).into();
 
-        for (y, line) in s.lines().enumerate() {
+        for (y, line) in rows.into_iter().enumerate() {
             for (x, c) in line.chars().enumerate() {
                 let position = (x, y).into();
                 match c {
@@ -113,12 +177,14 @@ impl FromStr for Lab {
                         map.insert(position);
                     }
                     '^' => {
-                        guard_position = position;
+                        guard_position = Some(position);
                     }
-                    _ => {}
+                    '.' => {}
+                    _ => return Err(text.char_error(line, x, "expected `.`, `#` or `^`").into()),
                 }
             }
         }
+        let guard_position = guard_position.ok_or(text.error(s, "no guard `^` on the map"))?;
         Ok(Lab {
             map,
             guard_position,
@@ -141,4 +207,26 @@ mod tests {
     fn part2() {
         assert_eq!(Day06.run_test2(), 6);
     }
+
+    #[test]
+    fn animation_ends_when_the_guard_leaves() {
+        let input = std::fs::read_to_string(crate::input::sample_path(2024, 6)).unwrap();
+        let lab = Day06::parse(&input).unwrap();
+        let mut walk = Day06::animation(1, &lab).unwrap();
+        while walk.step() {}
+
+        let frame = walk.frame();
+        let visited = frame.iter().filter(|(_, cell)| cell.glyph == 'X').count();
+        assert_eq!(visited, 41);
+        assert!(Day06::animation(2, &lab).is_none());
+    }
+
+    #[test]
+    fn malformed_maps_are_located() {
+        let error = Day06::parse("..#\n.x^\n").unwrap_err().to_string();
+        assert_eq!(error, "line 2, column 2: expected `.`, `#` or `^` at `x`");
+
+        let error = Day06::parse("..#\n...\n").unwrap_err().to_string();
+        assert!(error.contains("no guard"), "{}", error);
+    }
 }
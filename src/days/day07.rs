@@ -3,22 +3,22 @@ use std::{collections::VecDeque, str::FromStr};
 use anyhow::{Ok, Result};
 use rayon::prelude::*;
 
-use crate::solution::Solution;
+use crate::{parse::Text, solution::Solution};
 
 pub struct Day07;
 
 impl Solution for Day07 {
     type Answer = u64;
+    type Parsed<'a> = Vec<Equation>;
     fn day(&self) -> u8 {
         7
     }
 
-    fn part1(input: &str) -> Result<Self::Answer> {
-        let equations: Vec<Equation> = input
-            .lines()
-            .map(|line| line.parse())
-            .collect::<Result<_>>()?;
+    fn parse(input: &str) -> Result<Self::Parsed<'_>> {
+        Ok(Text::new(input).parse_lines()?)
+    }
 
+    fn part1(equations: &Vec<Equation>) -> Result<Self::Answer> {
         let possibly_true_results = equations
             .par_iter()
             .filter(|equation| equation.possibly_true::<false>())
@@ -28,12 +28,7 @@ impl Solution for Day07 {
         Ok(possibly_true_results)
     }
 
-    fn part2(input: &str) -> Result<Self::Answer> {
-        let equations: Vec<Equation> = input
-            .lines()
-            .map(|line| line.parse())
-            .collect::<Result<_>>()?;
-
+    fn part2(equations: &Vec<Equation>) -> Result<Self::Answer> {
         let possibly_true_results = equations
             .par_iter()
             .filter(|equation| equation.possibly_true::<true>())
@@ -45,7 +40,7 @@ impl Solution for Day07 {
 }
 
 #[derive(Clone, Debug)]
-struct Equation {
+pub struct Equation {
     result: u64,
     ns: Vec<u64>,
 }
@@ -104,12 +99,13 @@ impl FromStr for Equation {
     type Err = anyhow::Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (result_str, ns_str) = s.split_once(": ").expect("Invalid input");
-        let result = result_str.parse()?;
+        let text = Text::new(s);
+        let (result_str, ns_str) = text.split_once(s, ": ")?;
+        let result = text.parse(result_str)?;
         let ns = ns_str
             .split_whitespace()
-            .map(|n| Ok(n.parse()?))
-            .collect::<Result<Vec<u64>>>()?;
+            .map(|n| text.parse(n))
+            .collect::<Result<_, _>>()?;
         Ok(Equation { result, ns })
     }
 }
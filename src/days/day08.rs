@@ -6,18 +6,22 @@ use std::{
 use anyhow::Result;
 use itertools::Itertools;
 
-use crate::{solution::Solution, vector::Vec2};
+use crate::{parse::Text, solution::Solution, vector::Vec2};
 
 pub struct Day08;
 
 impl Solution for Day08 {
     type Answer = usize;
+    type Parsed<'a> = AntennaMap;
     fn day(&self) -> u8 {
         8
     }
 
-    fn part1(input: &str) -> Result<Self::Answer> {
-        let antenna_map = input.parse::<AntennaMap>()?;
+    fn parse(input: &str) -> Result<Self::Parsed<'_>> {
+        input.parse()
+    }
+
+    fn part1(antenna_map: &AntennaMap) -> Result<Self::Answer> {
         let total_antinodes = antenna_map
             .antenna_index
             .keys()
@@ -29,8 +33,7 @@ impl Solution for Day08 {
         Ok(total_antinodes)
     }
 
-    fn part2(input: &str) -> Result<Self::Answer> {
-        let antenna_map = input.parse::<AntennaMap>()?;
+    fn part2(antenna_map: &AntennaMap) -> Result<Self::Answer> {
         let total_antinodes = antenna_map
             .antenna_index
             .keys()
@@ -43,7 +46,7 @@ impl Solution for Day08 {
     }
 }
 
-struct AntennaMap {
+pub struct AntennaMap {
     antenna_index: HashMap<char, Vec<Vec2>>,
     size: Vec2,
 }
@@ -108,14 +111,12 @@ impl FromStr for AntennaMap {
     type Err = anyhow::Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let rows = Text::new(s).grid()?;
         let mut index = HashMap::new();
 
-        let size = Vec2::new(
-            s.lines().next().unwrap().len() as i64,
-            s.lines().count() as i64,
-        );
+        let size = Vec2::new(rows[0].chars().count() as i64, rows.len() as i64);
 
-        for (y, line) in s.lines().enumerate() {
+        for (y, line) in rows.into_iter().enumerate() {
             for (x, c) in line.chars().enumerate() {
                 match c {
                     '.' => continue,
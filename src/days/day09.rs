@@ -1,30 +1,35 @@
 use std::str::FromStr;
 
-use crate::solution::Solution;
+use crate::{parse::Text, solution::Solution};
 
 pub struct Day09;
 
 impl Solution for Day09 {
     type Answer = i64;
+    type Parsed<'a> = Disk;
     fn day(&self) -> u8 {
         9
     }
 
-    fn part1(input: &str) -> anyhow::Result<i64> {
-        let mut disk = input.parse::<Disk>()?;
+    fn parse(input: &str) -> anyhow::Result<Self::Parsed<'_>> {
+        input.parse()
+    }
+
+    fn part1(disk: &Disk) -> anyhow::Result<i64> {
+        let mut disk = disk.clone();
         disk.compact();
         Ok(disk.checksum() as i64)
     }
 
-    fn part2(input: &str) -> anyhow::Result<i64> {
-        let mut disk = input.parse::<Disk>()?;
+    fn part2(disk: &Disk) -> anyhow::Result<i64> {
+        let mut disk = disk.clone();
         disk.compact_full_files();
         Ok(disk.checksum())
     }
 }
 
-#[derive(Debug)]
-struct Disk {
+#[derive(Clone, Debug)]
+pub struct Disk {
     data: Vec<i32>,
 }
 
@@ -71,15 +76,16 @@ impl Disk {
 
             // Have all the information, now find a contiguous space to move the file to
             let mut space_cursor = 0;
-            while let Some((space_index, space_length)) =
-                self.find_next_contiguous_space(space_cursor)
-            {
-                // We've passed the file, search is over, move to the next file
-                if space_index > end_ptr {
+            loop {
+                // We've passed the file, or run out of space, search is over, move to the next file
+                let Some((space_index, space_length)) = self
+                    .find_next_contiguous_space(space_cursor)
+                    .filter(|&(space_index, _)| space_index < end_ptr)
+                else {
                     end_ptr -= file_length;
                     current_file = &self.data[end_ptr];
                     break;
-                }
+                };
 
                 // The space is too small, continue searching
                 if space_length < file_length {
@@ -103,7 +109,7 @@ impl Disk {
 
     fn find_next_contiguous_space(&self, start: usize) -> Option<(usize, usize)> {
         let mut cursor = start;
-        while self.data[cursor] != -1 {
+        while cursor < self.data.len() && self.data[cursor] != -1 {
             cursor += 1;
         }
         if cursor == self.data.len() {
@@ -133,11 +139,15 @@ impl FromStr for Disk {
     type Err = anyhow::Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let text = Text::new(s);
         let mut data = Vec::with_capacity(s.len() * 9);
         let mut id = 0;
         let mut is_file = true;
-        for c in s.chars() {
-            let length = c.to_digit(10).unwrap() as usize;
+        for (i, c) in s.trim_end().chars().enumerate() {
+            let length = c
+                .to_digit(10)
+                .ok_or_else(|| text.char_error(s, i, "expected a digit"))?
+                as usize;
             if is_file {
                 data.extend_from_slice(&[id].repeat(length));
                 id += 1;
@@ -146,6 +156,9 @@ impl FromStr for Disk {
             }
             is_file = !is_file;
         }
+        if data.iter().all(|&block| block == -1) {
+            return Err(text.error(s, "expected at least one file block").into());
+        }
 
         Ok(Disk { data })
     }
@@ -203,6 +216,25 @@ mod tests {
         );
     }
 
+    #[test]
+    fn disks_without_files_are_rejected() {
+        for input in ["", "\n", "0", "01"] {
+            let error = Day09::parse(input).unwrap_err().to_string();
+            assert!(
+                error.contains("expected at least one file block"),
+                "{}",
+                error
+            );
+        }
+    }
+
+    #[test]
+    fn disks_without_free_space_stay_put() {
+        let disk = Day09::parse("10101").unwrap();
+        assert_eq!(Day09::part1(&disk).unwrap(), 5);
+        assert_eq!(Day09::part2(&disk).unwrap(), 5);
+    }
+
     #[test]
     fn checksum() {
         let mut disk = "12345".parse::<Disk>().unwrap();
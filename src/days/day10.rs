@@ -3,20 +3,24 @@ use std::{
     str::FromStr,
 };
 
-use anyhow::Result;
+use anyhow::{anyhow, Result};
 
-use crate::{solution::Solution, vector::Vec2};
+use crate::{solution::Solution, vector::Vec2, vector_map::VectorMap};
 
 pub struct Day10;
 
 impl Solution for Day10 {
     type Answer = u64;
+    type Parsed<'a> = TopologicalMap;
     fn day(&self) -> u8 {
         10
     }
 
-    fn part1(input: &str) -> Result<Self::Answer> {
-        let map = input.parse::<TopologicalMap>()?;
+    fn parse(input: &str) -> Result<Self::Parsed<'_>> {
+        input.parse()
+    }
+
+    fn part1(map: &TopologicalMap) -> Result<Self::Answer> {
         Ok(map
             .zeros
             .iter()
@@ -24,8 +28,7 @@ impl Solution for Day10 {
             .sum())
     }
 
-    fn part2(input: &str) -> Result<Self::Answer> {
-        let map = input.parse::<TopologicalMap>()?;
+    fn part2(map: &TopologicalMap) -> Result<Self::Answer> {
         Ok(map
             .zeros
             .iter()
@@ -34,9 +37,8 @@ impl Solution for Day10 {
     }
 }
 
-struct TopologicalMap {
-    map: Vec<Vec<u8>>,
-    size: Vec2,
+pub struct TopologicalMap {
+    map: VectorMap<u8>,
     zeros: Vec<Vec2>,
 }
 
@@ -46,7 +48,7 @@ impl TopologicalMap {
         let mut total_trails = 0;
         let mut edge = VecDeque::from([start]);
         while let Some(pos) = edge.pop_front() {
-            let value = self.get(pos);
+            let value = self.map[pos];
             if value == 9 {
                 if DISTINCT && trails.insert(pos) {
                     total_trails += 1;
@@ -55,55 +57,31 @@ impl TopologicalMap {
                 }
                 continue;
             }
-            let candidates = pos.get_surrounding_4();
-
-            candidates
-                .iter()
-                .filter(|&c| c.contained_in(Vec2::new(0, 0), self.size))
-                .filter(|&c| self.get(*c) == value + 1)
-                .for_each(|c| {
-                    edge.push_back(*c);
+
+            self.map
+                .neighbors4(pos)
+                .filter(|&(_, &height)| height == value + 1)
+                .for_each(|(c, _)| {
+                    edge.push_back(c);
                 });
         }
 
         total_trails
     }
-
-    fn get(&self, pos: Vec2) -> u8 {
-        self.map[pos.y as usize][pos.x as usize]
-    }
 }
 
 impl FromStr for TopologicalMap {
     type Err = anyhow::Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let map: Vec<Vec<u8>> = s
-            .lines()
-            .map(|line| {
-                line.chars()
-                    .map(|c| c.to_digit(10).map(|d| d as u8))
-                    .collect::<Option<Vec<_>>>()
-            })
-            .collect::<Option<Vec<_>>>()
-            .unwrap();
-
-        let size = (map[0].len(), map.len()).into();
-        let zeros = map
-            .iter()
-            .enumerate()
-            .flat_map(|(y, row)| {
-                row.iter()
-                    .enumerate()
-                    .filter_map(|(x, &c)| match c {
-                        0 => Some((x, y).into()),
-                        _ => None,
-                    })
-                    .collect::<Vec<_>>()
-            })
-            .collect();
-
-        Ok(TopologicalMap { map, size, zeros })
+        let (map, markers) = VectorMap::parse_with(s, "0", |c, _| {
+            c.to_digit(10)
+                .map(|d| d as u8)
+                .ok_or(anyhow!("expected a height from 0 to 9"))
+        })?;
+        let zeros = markers.all('0').to_vec();
+
+        Ok(TopologicalMap { map, zeros })
     }
 }
 
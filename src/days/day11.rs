@@ -2,25 +2,28 @@ use std::{collections::HashMap, str::FromStr};
 
 use anyhow::Result;
 
-use crate::solution::Solution;
+use crate::{parse::Text, solution::Solution};
 
 pub struct Day11;
 
 impl Solution for Day11 {
     type Answer = u64;
+    type Parsed<'a> = StoneArrangement;
     fn day(&self) -> u8 {
         11
     }
 
-    fn part1(input: &str) -> Result<Self::Answer> {
-        let arrangement = input.parse::<StoneArrangement>()?;
+    fn parse(input: &str) -> Result<Self::Parsed<'_>> {
+        input.parse()
+    }
+
+    fn part1(arrangement: &StoneArrangement) -> Result<Self::Answer> {
         let final_stones = arrangement.blinks(25);
 
         Ok(final_stones)
     }
 
-    fn part2(input: &str) -> Result<Self::Answer> {
-        let arrangement = input.parse::<StoneArrangement>()?;
+    fn part2(arrangement: &StoneArrangement) -> Result<Self::Answer> {
         let final_stones = arrangement.blinks(75);
 
         Ok(final_stones)
@@ -28,7 +31,7 @@ impl Solution for Day11 {
 }
 
 #[derive(Clone, Debug)]
-struct StoneArrangement {
+pub struct StoneArrangement {
     stones: HashMap<u64, u64>,
 }
 
@@ -71,9 +74,10 @@ impl FromStr for StoneArrangement {
     type Err = anyhow::Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let text = Text::new(s);
         let stones = s
             .split_whitespace()
-            .map(|s| s.parse().map(|i| (i, 1)))
+            .map(|stone| text.parse(stone).map(|i| (i, 1)))
             .collect::<Result<_, _>>()?;
         Ok(Self { stones })
     }
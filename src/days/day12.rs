@@ -5,25 +5,33 @@ use std::{
 
 use anyhow::Result;
 
-use crate::{solution::Solution, vector::Vec2, vector_map::VectorMap};
+use crate::{
+    render::{Cell, Color, Overlay, Renderer},
+    solution::Solution,
+    vector::Vec2,
+    vector_map::VectorMap,
+};
 
 pub struct Day12;
 
 impl Solution for Day12 {
     type Answer = usize;
+    type Parsed<'a> = Garden;
     fn day(&self) -> u8 {
         12
     }
 
-    fn part1(input: &str) -> Result<Self::Answer> {
-        let garden = input.parse::<Garden>()?;
+    fn parse(input: &str) -> Result<Self::Parsed<'_>> {
+        input.parse()
+    }
+
+    fn part1(garden: &Garden) -> Result<Self::Answer> {
         let regions = garden.find_all_regions();
 
         Ok(regions.iter().map(|region| region.price()).sum())
     }
 
-    fn part2(input: &str) -> Result<Self::Answer> {
-        let garden = input.parse::<Garden>()?;
+    fn part2(garden: &Garden) -> Result<Self::Answer> {
         let regions = garden.find_all_regions();
 
         let result = regions
@@ -34,7 +42,7 @@ impl Solution for Day12 {
     }
 }
 
-struct Garden {
+pub struct Garden {
     map: VectorMap<char>,
 }
 
@@ -60,7 +68,7 @@ impl Garden {
         let mut region = HashSet::new();
         let mut to_visit = VecDeque::from([pos]);
 
-        let plant = *self.map.get(&pos).unwrap();
+        let plant = self.map[pos];
 
         while let Some(curr_pos) = to_visit.pop_front() {
             if region.contains(&curr_pos) {
@@ -81,6 +89,16 @@ impl Garden {
         Region(plant, region)
     }
 
+    /// The garden with each region in a color of its own.
+    pub fn render(&self) -> Renderer<'_, char> {
+        self.find_all_regions().into_iter().enumerate().fold(
+            Renderer::new(&self.map, |&plant| Cell::new(plant)),
+            |renderer, (index, Region(_, positions))| {
+                renderer.overlay(Overlay::color(positions, Color::palette(index)))
+            },
+        )
+    }
+
     fn count_corners(&self, region: &Region) -> usize {
         let mut corners = 0;
         let mut triple_corners = 0;
@@ -214,4 +232,15 @@ mod tests {
     fn part2_example() {
         assert_eq!(Day12.run_test2(), 1206)
     }
+
+    #[test]
+    fn regions_are_colored_apart() {
+        let garden: Garden = "AAB\nCAB".parse().unwrap();
+        let cells = garden.render().cells();
+        let a = cells[Vec2::new(0, 0)].color;
+        assert_eq!(cells[Vec2::new(1, 1)].color, a);
+        assert_ne!(cells[Vec2::new(2, 0)].color, a);
+        assert_ne!(cells[Vec2::new(0, 1)].color, a);
+        assert_eq!(garden.render().text(), "AAB\nCAB\n");
+    }
 }
@@ -1,35 +1,40 @@
 use std::str::FromStr;
 
 use anyhow::Result;
-use itertools::Itertools;
 
-use crate::{solution::Solution, vector::Vec2};
+use crate::{
+    parse::{ParseError, Text},
+    solution::Solution,
+    vector::Vec2,
+};
 
 pub struct Day13;
 
 impl Solution for Day13 {
     type Answer = f64;
+    type Parsed<'a> = Vec<ArcadeMachine>;
     fn day(&self) -> u8 {
         13
     }
 
-    fn part1(input: &str) -> anyhow::Result<f64> {
-        let machines = input
-            .split("\n\n")
-            .map(|s| s.parse::<ArcadeMachine>().unwrap());
+    fn parse(input: &str) -> Result<Self::Parsed<'_>> {
+        let text = Text::new(input);
+        Ok(text
+            .sections()
+            .map(|section| text.parse(section))
+            .collect::<Result<_, _>>()?)
+    }
 
-        let prize_presses = machines.filter_map(|machine| machine.find_prize());
+    fn part1(machines: &Vec<ArcadeMachine>) -> anyhow::Result<f64> {
+        let prize_presses = machines.iter().filter_map(|machine| machine.find_prize());
 
         let total_cost = prize_presses.map(|(a, b)| a * 3.0 + b).sum();
 
         Ok(total_cost)
     }
 
-    fn part2(input: &str) -> anyhow::Result<f64> {
-        let mut machines = input
-            .split("\n\n")
-            .map(|s| s.parse::<ArcadeMachine>().unwrap())
-            .collect::<Vec<_>>();
+    fn part2(machines: &Vec<ArcadeMachine>) -> anyhow::Result<f64> {
+        let mut machines = machines.clone();
 
         machines
             .iter_mut()
@@ -43,7 +48,8 @@ impl Solution for Day13 {
     }
 }
 
-struct ArcadeMachine {
+#[derive(Clone)]
+pub struct ArcadeMachine {
     a_button: Vec2,
     b_button: Vec2,
     prize: Vec2,
@@ -79,33 +85,29 @@ impl FromStr for ArcadeMachine {
     type Err = anyhow::Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let text = Text::new(s);
         let mut lines = s.lines();
-        let a_line = lines.next().unwrap();
-        let b_line = lines.next().unwrap();
-        let prize_line = lines.next().unwrap();
-
-        let a_vec = line_to_vec(a_line);
-        let b_vec = line_to_vec(b_line);
-        let prize_vec = line_to_vec(prize_line);
+        let mut next_vec = |label: &str| {
+            let line = lines
+                .next()
+                .ok_or_else(|| text.error(s, format!("expected a `{}` line", label.trim())))?;
+            line_to_vec(&text, line, label)
+        };
 
         Ok(ArcadeMachine {
-            a_button: a_vec,
-            b_button: b_vec,
-            prize: prize_vec,
+            a_button: next_vec("Button A: ")?,
+            b_button: next_vec("Button B: ")?,
+            prize: next_vec("Prize: ")?,
         })
     }
 }
 
-fn line_to_vec(s: &str) -> Vec2 {
-    let parts = s
-        .split_whitespace()
-        .filter(|part| part.contains("+") || part.contains("="))
-        .map(|part| part.trim_matches(&['X', 'Y', '+', '=', ',']))
-        .map(|part| part.parse::<i64>().unwrap())
-        .collect_tuple::<(_, _)>()
-        .unwrap();
-
-    parts.into()
+fn line_to_vec(text: &Text, line: &str, label: &str) -> Result<Vec2, ParseError> {
+    let coordinates = text.strip_prefix(line, label)?;
+    let (x, y) = text.split_once(coordinates, ", ")?;
+    let x = text.strip_prefix(x, "X")?.trim_start_matches(['+', '=']);
+    let y = text.strip_prefix(y, "Y")?.trim_start_matches(['+', '=']);
+    Ok(Vec2::new(text.parse(x)?, text.parse(y)?))
 }
 
 #[cfg(test)]
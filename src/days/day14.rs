@@ -1,26 +1,35 @@
 use std::{collections::HashSet, str::FromStr};
 
-use anyhow::anyhow;
-
-use crate::{solution::Solution, vector::Vec2};
+use crate::{
+    animate::{Animate, Frame},
+    parse::Text,
+    render::{Cell, Color},
+    solution::Solution,
+    vector::Vec2,
+    vector_map::VectorMap,
+};
 
 pub struct Day14;
 
 impl Solution for Day14 {
     type Answer = i64;
+    type Parsed<'a> = Board;
     fn day(&self) -> u8 {
         14
     }
 
-    fn part1(input: &str) -> anyhow::Result<i64> {
-        let board = input.parse::<Board>()?;
+    fn parse(input: &str) -> anyhow::Result<Self::Parsed<'_>> {
+        input.parse()
+    }
+
+    fn part1(board: &Board) -> anyhow::Result<i64> {
         let board_after_100 = board.calculate_robots_after(100);
         let safety_score = board_after_100.calculate_safety_score();
         Ok(safety_score)
     }
 
-    fn part2(input: &str) -> anyhow::Result<i64> {
-        let mut board = input.parse::<Board>()?;
+    fn part2(board: &Board) -> anyhow::Result<i64> {
+        let mut board = board.clone();
         let mut seconds = 0;
         while !board.robots_all_in_unique_positions() {
             board = board.calculate_robots_after(1);
@@ -28,9 +37,59 @@ impl Solution for Day14 {
         }
         Ok(seconds)
     }
+
+    fn animation<'a>(part: u8, board: &'a Board) -> Option<Box<dyn Animate + 'a>> {
+        let limit = match part {
+            1 => Some(100),
+            2 => None,
+            _ => return None,
+        };
+        Some(Box::new(RobotMotion {
+            board: board.clone(),
+            seconds: 0,
+            limit,
+        }))
+    }
+}
+
+/// The robots moving a second at a time, for `limit` seconds or, without one, until no two
+/// robots share a tile.
+pub struct RobotMotion {
+    board: Board,
+    seconds: i64,
+    limit: Option<i64>,
 }
 
-struct Board {
+impl Animate for RobotMotion {
+    fn frame(&self) -> Frame {
+        let mut counts = VectorMap::new(self.board.size, 0u32);
+        for robot in &self.board.robots {
+            counts[robot.position] += 1;
+        }
+        counts.map(|&count| match count {
+            0 => Cell::new('.'),
+            1..=9 => Cell::colored(char::from_digit(count, 10).unwrap(), Color::GREEN),
+            _ => Cell::colored('+', Color::GREEN),
+        })
+    }
+
+    fn step(&mut self) -> bool {
+        let done = match self.limit {
+            Some(limit) => self.seconds >= limit,
+            None => self.board.robots_all_in_unique_positions(),
+        };
+        if done {
+            return false;
+        }
+
+        self.board = self.board.calculate_robots_after(1);
+        self.seconds += 1;
+        true
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Board {
     size: Vec2,
     robots: Vec<Robot>,
 }
@@ -96,24 +155,36 @@ impl FromStr for Board {
     type Err = anyhow::Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (size_str, robots_str) = s
-            .split_once("\n\n")
-            .ok_or(anyhow!("Invalid board format"))?;
-
-        let (size_x, size_y) = size_str
-            .split_once("x")
-            .ok_or(anyhow!("Invalid board size format"))?;
-        let size = Vec2::new(size_x.parse()?, size_y.parse()?);
+        let text = Text::new(s);
+        let (size_str, robots_str) = text.split_once(s, "\n\n")?;
+
+        let (size_x, size_y) = text.split_once(size_str, "x")?;
+        let size = Vec2::new(text.parse(size_x)?, text.parse(size_y)?);
+        if size.x <= 0 || size.y <= 0 {
+            return Err(text
+                .error(size_str, "expected a size above 0 in both directions")
+                .into());
+        }
 
         let robots = robots_str
             .lines()
-            .map(|line| line.parse())
+            .map(|line| {
+                let robot: Robot = text.parse(line)?;
+                if !robot.position.contained_in(Vec2::new(0, 0), size) {
+                    return Err(text.error(
+                        line,
+                        format!("robot outside the {}×{} board", size.x, size.y),
+                    ));
+                }
+                Ok(robot)
+            })
             .collect::<Result<Vec<Robot>, _>>()?;
 
         Ok(Board { size, robots })
     }
 }
 
+#[derive(Clone, Debug)]
 struct Robot {
     position: Vec2,
     velocity: Vec2,
@@ -123,19 +194,10 @@ impl FromStr for Robot {
     type Err = anyhow::Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (p_str, v_str) = s.split_once(" ").ok_or(anyhow!("Invalid robot format"))?;
-
-        let p_comma = p_str.trim_matches(&['p', '=']);
-        let (p_x, p_y) = p_comma
-            .split_once(",")
-            .ok_or(anyhow!("Invalid robot position format"))?;
-        let p_vec = Vec2::new(p_x.parse()?, p_y.parse()?);
-
-        let v_comma = v_str.trim_matches(&['v', '=']);
-        let (v_x, v_y) = v_comma
-            .split_once(",")
-            .ok_or(anyhow!("Invalid robot velocity format"))?;
-        let v_vec = Vec2::new(v_x.parse()?, v_y.parse()?);
+        let text = Text::new(s);
+        let (p_str, v_str) = text.split_once(s, " ")?;
+        let p_vec = text.parse(text.strip_prefix(p_str, "p=")?)?;
+        let v_vec = text.parse(text.strip_prefix(v_str, "v=")?)?;
 
         Ok(Robot {
             position: p_vec,
@@ -152,4 +214,35 @@ mod tests {
     fn test_part1() {
         assert_eq!(Day14.run_test1(), 12);
     }
+
+    #[test]
+    fn animation_runs_for_a_hundred_seconds() {
+        let input = std::fs::read_to_string(crate::input::sample_path(2024, 14)).unwrap();
+        let board = Day14::parse(&input).unwrap();
+        let mut motion = Day14::animation(1, &board).unwrap();
+        let mut steps = 0;
+        while motion.step() {
+            steps += 1;
+        }
+        assert_eq!(steps, 100);
+
+        let frame = motion.frame();
+        let robots: u32 = frame
+            .iter()
+            .filter_map(|(_, cell)| cell.glyph.to_digit(10))
+            .sum();
+        assert_eq!(robots as usize, board.robots.len());
+    }
+
+    #[test]
+    fn malformed_boards_are_located() {
+        let error = Day14::parse("0x0\n\np=0,0 v=1,1").unwrap_err().to_string();
+        assert!(error.starts_with("line 1, column 1:"), "{}", error);
+
+        let error = Day14::parse("11x7\n\np=0,0 v=1,1\np=11,3 v=1,1")
+            .unwrap_err()
+            .to_string();
+        assert!(error.starts_with("line 4, column 1:"), "{}", error);
+        assert!(error.contains("outside"), "{}", error);
+    }
 }
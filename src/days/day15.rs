@@ -5,31 +5,56 @@ use std::{
 
 use anyhow::anyhow;
 
-use crate::{solution::Solution, vector::Vec2, vector_map::VectorMap};
+use crate::{
+    animate::{Animate, Frame},
+    parse::Text,
+    render::{Cell, Color, Overlay, Renderer},
+    solution::Solution,
+    vector::Vec2,
+    vector_map::VectorMap,
+};
 
 pub struct Day15;
 
 impl Solution for Day15 {
     type Answer = i64;
+    type Parsed<'a> = Warehouse;
     fn day(&self) -> u8 {
         15
     }
 
-    fn part1(input: &str) -> anyhow::Result<i64> {
-        let mut warehouse = input.parse::<Warehouse>()?;
+    fn parse(input: &str) -> anyhow::Result<Self::Parsed<'_>> {
+        input.parse()
+    }
+
+    fn part1(warehouse: &Warehouse) -> anyhow::Result<i64> {
+        let mut warehouse = warehouse.clone();
         warehouse.run_instructions();
         Ok(warehouse.gps_score())
     }
 
-    fn part2(input: &str) -> anyhow::Result<i64> {
-        let mut warehouse = input.parse::<Warehouse>()?;
-        warehouse = warehouse.widen();
+    fn part2(warehouse: &Warehouse) -> anyhow::Result<i64> {
+        let mut warehouse = warehouse.widen();
         warehouse.run_instructions_wide();
         Ok(warehouse.gps_score())
     }
+
+    fn animation<'a>(part: u8, warehouse: &'a Warehouse) -> Option<Box<dyn Animate + 'a>> {
+        let (warehouse, wide) = match part {
+            1 => (warehouse.clone(), false),
+            2 => (warehouse.widen(), true),
+            _ => return None,
+        };
+        Some(Box::new(RobotPushes {
+            warehouse,
+            wide,
+            next: 0,
+        }))
+    }
 }
 
-struct Warehouse {
+#[derive(Clone, Debug)]
+pub struct Warehouse {
     map: VectorMap<char>,
     robot_position: Vec2,
     instructions: Vec<Vec2>,
@@ -58,8 +83,8 @@ impl Warehouse {
                     break;
                 }
             }
-            Some('#') => {}
-            _ => panic!("Invalid position"),
+            // Walls, and anything off the map, stop the robot.
+            _ => {}
         }
     }
 
@@ -83,13 +108,13 @@ impl Warehouse {
                 match direction {
                     Vec2::RIGHT | Vec2::LEFT => {
                         let mut path = new_position + (direction * 2);
-                        while let Some(&tile) = self.map.get(&path) {
-                            match tile {
-                                '#' => {
+                        loop {
+                            match self.map.get(&path) {
+                                Some('#') | None => {
                                     blocked = true;
                                     break;
                                 }
-                                '[' | ']' => {
+                                Some('[' | ']') => {
                                     boxes.push(path);
                                     path = path + direction;
                                 }
@@ -107,7 +132,7 @@ impl Warehouse {
                                 let path = b + direction;
 
                                 match self.map.get(&path) {
-                                    Some('#') => {
+                                    Some('#') | None => {
                                         blocked = true;
                                         next.clear();
                                         break;
@@ -147,8 +172,8 @@ impl Warehouse {
                     self.robot_position = new_position;
                 }
             }
-            Some('#') => {}
-            _ => panic!("Invalid position"),
+            // Walls, and anything off the map, stop the robot.
+            _ => {}
         }
     }
 
@@ -210,27 +235,30 @@ impl FromStr for Warehouse {
     type Err = anyhow::Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (map_str, instructions_str) = s.split_once("\n\n").ok_or(anyhow!("Invalid input"))?;
+        let text = Text::new(s);
+        let (map_str, instructions_str) = text.split_once(s, "\n\n")?;
 
-        let mut map = map_str.parse::<VectorMap<char>>()?;
-        let robot_position = map
-            .iter()
-            .find(|(_, &c)| c == '@')
-            .map(|(pos, _)| pos)
-            .unwrap();
-        map.set(&robot_position, '.');
+        let (map, markers) = VectorMap::parse_with(map_str, "@", |c, _| match c {
+            '#' | '.' | 'O' => Ok(c),
+            '@' => Ok('.'),
+            _ => Err(anyhow!("expected `#`, `.`, `O` or `@`")),
+        })?;
+        let robot_position = markers.one('@')?;
 
         let instructions = instructions_str
-            .chars()
-            .filter(|c| ['^', 'v', '<', '>'].contains(c))
-            .map(|c| match c {
-                '^' => Vec2::new(0, -1),
-                'v' => Vec2::new(0, 1),
-                '<' => Vec2::new(-1, 0),
-                '>' => Vec2::new(1, 0),
-                _ => panic!("Invalid instruction"),
+            .char_indices()
+            .filter(|(_, c)| !c.is_whitespace())
+            .map(|(i, c)| match c {
+                '^' => Ok(Vec2::new(0, -1)),
+                'v' => Ok(Vec2::new(0, 1)),
+                '<' => Ok(Vec2::new(-1, 0)),
+                '>' => Ok(Vec2::new(1, 0)),
+                _ => Err(text.error(
+                    &instructions_str[i..i + c.len_utf8()],
+                    "expected `^`, `v`, `<` or `>`",
+                )),
             })
-            .collect();
+            .collect::<Result<_, _>>()?;
 
         Ok(Warehouse {
             map,
@@ -240,6 +268,44 @@ impl FromStr for Warehouse {
     }
 }
 
+/// The robot following its instructions one move at a time.
+pub struct RobotPushes {
+    warehouse: Warehouse,
+    wide: bool,
+    next: usize,
+}
+
+impl Animate for RobotPushes {
+    fn frame(&self) -> Frame {
+        self.warehouse.render().cells()
+    }
+
+    fn step(&mut self) -> bool {
+        let Some(&direction) = self.warehouse.instructions.get(self.next) else {
+            return false;
+        };
+        if self.wide {
+            self.warehouse.move_robot_wide(direction);
+        } else {
+            self.warehouse.move_robot(direction);
+        }
+        self.next += 1;
+        true
+    }
+}
+
+impl Warehouse {
+    /// The warehouse with boxes, walls and the robot in color.
+    pub fn render(&self) -> Renderer<'_, char> {
+        Renderer::new(&self.map, |&c| match c {
+            '#' => Cell::colored(c, Color::GRAY),
+            'O' | '[' | ']' => Cell::colored(c, Color::YELLOW),
+            _ => Cell::new(c),
+        })
+        .overlay(Overlay::color([self.robot_position], Color::RED).glyph('@'))
+    }
+}
+
 impl Display for Warehouse {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         let mut map = self.map.clone();
@@ -262,6 +328,51 @@ mod tests {
         assert_eq!(Day15.run_test2(), 9021);
     }
 
+    #[test]
+    fn animation_follows_every_instruction() {
+        let input = std::fs::read_to_string(crate::input::sample_path(2024, 15)).unwrap();
+        let warehouse = Day15::parse(&input).unwrap();
+        for (part, score) in [(1, 10092), (2, 9021)] {
+            let mut pushes = Day15::animation(part, &warehouse).unwrap();
+            let mut steps = 0;
+            while pushes.step() {
+                steps += 1;
+            }
+            assert_eq!(steps, warehouse.instructions.len());
+
+            let frame = pushes.frame();
+            let gps: i64 = frame
+                .iter()
+                .filter(|(_, cell)| matches!(cell.glyph, 'O' | '['))
+                .map(|(pos, _)| pos.x + 100 * pos.y)
+                .sum();
+            assert_eq!(gps, score);
+        }
+    }
+
+    #[test]
+    fn malformed_maps_are_located() {
+        let error = Day15::parse("#####\n#@x.#\n#####\n\n>>")
+            .unwrap_err()
+            .to_string();
+        assert_eq!(
+            error,
+            "line 2, column 3: expected `#`, `.`, `O` or `@` at `x`"
+        );
+
+        let error = Day15::parse("#@.@#\n\n<").unwrap_err().to_string();
+        assert!(error.contains("more than one `@`"), "{}", error);
+        let error = Day15::parse("#...#\n\n<").unwrap_err().to_string();
+        assert!(error.contains("no `@`"), "{}", error);
+    }
+
+    #[test]
+    fn robots_stop_at_the_edge_of_unwalled_maps() {
+        let warehouse = Day15::parse("@.\n.O\n\n<^>>vv>").unwrap();
+        assert_eq!(Day15::part1(&warehouse).unwrap(), 101);
+        assert_eq!(Day15::part2(&warehouse).unwrap(), 102);
+    }
+
     #[test]
     fn test_small_example() {
         let input = r#"########
@@ -1,38 +1,51 @@
-use std::{
-    collections::{BinaryHeap, HashMap, HashSet},
-    str::FromStr,
-};
+use std::{collections::HashSet, str::FromStr};
 
-use anyhow::Result;
+use anyhow::{anyhow, Result};
 
-use crate::{solution::Solution, vector::Vec2, vector_map::VectorMap};
+use crate::{
+    render::{Cell, Color, Overlay, Renderer},
+    search::{self, ShortestPaths},
+    solution::Solution,
+    vector::Vec2,
+    vector_map::VectorMap,
+};
 
 pub struct Day16;
 
 impl Solution for Day16 {
     type Answer = u64;
+    type Parsed<'a> = Maze;
     fn day(&self) -> u8 {
         16
     }
 
-    fn part1(input: &str) -> Result<Self::Answer> {
-        let maze = input.parse::<Maze>()?;
-        Ok(maze.find_shortest_path()[0].cost)
+    fn parse(input: &str) -> Result<Self::Parsed<'_>> {
+        input.parse()
+    }
+
+    fn part1(maze: &Maze) -> Result<Self::Answer> {
+        maze.find_shortest_path()
+            .cost()
+            .ok_or(anyhow!("No path found"))
     }
 
-    fn part2(input: &str) -> Result<Self::Answer> {
-        let maze = input.parse::<Maze>()?;
+    fn part2(maze: &Maze) -> Result<Self::Answer> {
         let paths = maze.find_shortest_path();
         let tiles = paths
+            .states_on_paths()
             .into_iter()
-            .flat_map(|p| p.visited_tiles)
+            .map(|(position, _)| position)
             .collect::<HashSet<_>>();
 
         Ok(tiles.len() as u64)
     }
 }
 
-struct Maze {
+/// Where the reindeer is and which way it faces.
+pub type Reindeer = (Vec2, Vec2);
+
+/// A reindeer maze: moving forward costs 1, turning 90 degrees costs 1000.
+pub struct Maze {
     map: VectorMap<bool>,
     target: Vec2,
     position: Vec2,
@@ -40,75 +53,44 @@ struct Maze {
 }
 
 impl Maze {
-    fn find_shortest_path(&self) -> Vec<Move> {
-        let mut p_queue = BinaryHeap::new();
-        let mut visited = HashMap::new();
-        let mut optimal_cost = u64::MAX;
-        let mut paths = Vec::new();
-        p_queue.push(Move {
-            position: self.position,
-            direction: self.direction,
-            cost: 0,
-            visited_tiles: vec![self.position],
-        });
-
-        while let Some(current_move) = p_queue.pop() {
-            // End condition
-            if current_move.position == self.target {
-                if current_move.cost > optimal_cost {
-                    break;
-                }
-                optimal_cost = current_move.cost;
-                paths.push(current_move.clone());
-            }
-
-            // I'm on a wall
-            match self.map.get(&current_move.position) {
-                Some(true) | None => continue,
-                _ => {}
-            }
-
-            if let Some(&cost) = visited.get(&(current_move.position, current_move.direction)) {
-                if cost < current_move.cost {
-                    continue;
-                }
-            }
-
-            visited.insert(
-                (current_move.position, current_move.direction),
-                current_move.cost,
-            );
-
-            let mut new_visited_tiles = current_move.visited_tiles.clone();
-            new_visited_tiles.push(current_move.position + current_move.direction);
-
-            let next_moves = [
-                Move {
-                    position: current_move.position + current_move.direction,
-                    direction: current_move.direction,
-                    cost: current_move.cost + 1,
-                    visited_tiles: new_visited_tiles,
-                },
-                Move {
-                    position: current_move.position,
-                    direction: current_move.direction.rotate_clockwise(),
-                    cost: current_move.cost + 1000,
-                    visited_tiles: current_move.visited_tiles.clone(),
-                },
-                Move {
-                    position: current_move.position,
-                    direction: current_move.direction.rotate_counter_clockwise(),
-                    cost: current_move.cost + 1000,
-                    visited_tiles: current_move.visited_tiles,
-                },
-            ];
-
-            for next_move in next_moves.into_iter() {
-                p_queue.push(next_move);
-            }
-        }
-
-        paths
+    /// Every cheapest path from the start to the target.
+    pub fn find_shortest_path(&self) -> ShortestPaths<Reindeer, u64> {
+        search::all_shortest_paths(
+            [(self.position, self.direction)],
+            |&reindeer| self.moves(reindeer),
+            |&(position, _)| position == self.target,
+        )
+    }
+
+    /// The maze with every tile on one of the cheapest paths highlighted.
+    pub fn render(&self) -> Renderer<'_, bool> {
+        let tiles: Vec<_> = self
+            .find_shortest_path()
+            .states_on_paths()
+            .into_iter()
+            .map(|(position, _)| position)
+            .collect();
+
+        Renderer::new(&self.map, |&wall| match wall {
+            true => Cell::colored('#', Color::GRAY),
+            false => Cell::new('.'),
+        })
+        .overlay(Overlay::color(tiles, Color::GREEN).glyph('O'))
+        .overlay(Overlay::color([self.position], Color::RED).glyph('S'))
+        .overlay(Overlay::color([self.target], Color::RED).glyph('E'))
+    }
+
+    fn moves(&self, (position, direction): Reindeer) -> impl Iterator<Item = (Reindeer, u64)> {
+        let forward = position + direction;
+        let step = match self.map.get(&forward) {
+            Some(false) => Some(((forward, direction), 1)),
+            _ => None,
+        };
+
+        step.into_iter().chain([
+            ((position, direction.rotate_clockwise()), 1000),
+            ((position, direction.rotate_counter_clockwise()), 1000),
+        ])
     }
 }
 
@@ -116,53 +98,21 @@ impl FromStr for Maze {
     type Err = anyhow::Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let width = s.lines().next().unwrap().len();
-        let height = s.lines().count();
-
-        let mut map = VectorMap::new((width, height).into(), false);
-        let mut target = None;
-        let mut position = None;
-
-        for (y, line) in s.lines().enumerate() {
-            for (x, c) in line.chars().enumerate() {
-                match c {
-                    '#' => map.set(&(x, y).into(), true),
-                    'S' => position = Some((x, y).into()),
-                    'E' => target = Some((x, y).into()),
-                    _ => {}
-                }
-            }
-        }
+        let (map, markers) = VectorMap::parse_with(s, "SE", |c, _| match c {
+            '#' => Ok(true),
+            '.' | 'S' | 'E' => Ok(false),
+            _ => Err(anyhow!("expected `.`, `#`, `S` or `E`")),
+        })?;
 
         Ok(Self {
             map,
-            target: target.unwrap(),
-            position: position.unwrap(),
+            target: markers.one('E')?,
+            position: markers.one('S')?,
             direction: Vec2::RIGHT,
         })
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
-struct Move {
-    position: Vec2,
-    direction: Vec2,
-    visited_tiles: Vec<Vec2>,
-    cost: u64,
-}
-
-impl PartialOrd for Move {
-    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        Some(other.cost.cmp(&self.cost))
-    }
-}
-
-impl Ord for Move {
-    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        other.cost.cmp(&self.cost)
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -176,4 +126,10 @@ mod tests {
     fn part2_example() {
         assert_eq!(Day16.run_test2(), 64);
     }
+
+    #[test]
+    fn renders_best_paths() {
+        let maze: Maze = "#####\n#S.E#\n#.#.#\n#####".parse().unwrap();
+        assert_eq!(maze.render().text(), "#####\n#SOE#\n#.#.#\n#####\n");
+    }
 }
@@ -2,18 +2,23 @@ use std::{collections::HashSet, str::FromStr};
 
 use anyhow::{anyhow, Result};
 
-use crate::solution::Solution;
+use crate::{parse::Text, solution::Solution};
 
 pub struct Day17;
 
 impl Solution for Day17 {
     type Answer = String;
+    type Parsed<'a> = ThreeBitComputer;
     fn day(&self) -> u8 {
         17
     }
 
-    fn part1(input: &str) -> Result<Self::Answer> {
-        let mut computer = input.parse::<ThreeBitComputer>()?;
+    fn parse(input: &str) -> Result<Self::Parsed<'_>> {
+        input.parse()
+    }
+
+    fn part1(computer: &ThreeBitComputer) -> Result<Self::Answer> {
+        let mut computer = computer.clone();
         computer.run_assmebled_program();
         let output = computer
             .output
@@ -25,16 +30,17 @@ impl Solution for Day17 {
         Ok(output)
     }
 
-    fn part2(input: &str) -> Result<Self::Answer> {
-        let computer = input.parse::<ThreeBitComputer>()?;
-        let min_a_reg = computer.find_minimal_a_reg();
+    fn part2(computer: &ThreeBitComputer) -> Result<Self::Answer> {
+        let min_a_reg = computer
+            .find_minimal_a_reg()
+            .ok_or(anyhow!("No value of register A outputs the program"))?;
 
         Ok(min_a_reg.to_string())
     }
 }
 
-#[derive(Clone)]
-struct ThreeBitComputer {
+#[derive(Clone, Debug)]
+pub struct ThreeBitComputer {
     a_reg: u64,
 
     output: Vec<u8>,
@@ -53,7 +59,7 @@ impl ThreeBitComputer {
         }
     }
 
-    fn find_minimal_a_reg(&self) -> u64 {
+    fn find_minimal_a_reg(&self) -> Option<u64> {
         let mut candidates = HashSet::new();
         candidates.insert(0);
         for num in self.program.iter().rev() {
@@ -68,7 +74,7 @@ impl ThreeBitComputer {
             }
             candidates = new_candidates;
         }
-        *candidates.iter().min().unwrap()
+        candidates.into_iter().min()
     }
 
     fn calc_step(a: u64) -> u64 {
@@ -85,21 +91,29 @@ impl FromStr for ThreeBitComputer {
     type Err = anyhow::Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let text = Text::new(s);
         let mut a_reg = 0;
         let mut program = Vec::new();
 
         for line in s.lines().filter(|l| !l.is_empty()) {
-            match line.split_once(": ") {
-                Some(("Register A", value)) => a_reg = value.parse()?,
-                Some(("Register B", _)) => {}
-                Some(("Register C", _)) => {}
-                Some(("Program", value)) => {
+            match text.split_once(line, ": ")? {
+                ("Register A", value) => a_reg = text.parse(value)?,
+                ("Register B", _) => {}
+                ("Register C", _) => {}
+                ("Program", value) => {
                     program = value
                         .split(",")
-                        .map(|v| v.parse())
+                        .map(|v| match text.parse(v)? {
+                            value @ 0..=7 => Ok(value),
+                            _ => Err(text.error(v.trim(), "expected a 3-bit value, 0 to 7")),
+                        })
                         .collect::<Result<_, _>>()?;
                 }
-                v => return Err(anyhow!("Invalid input: {:?}", v)),
+                (label, _) => {
+                    return Err(text
+                        .error(label, "expected `Register A`, `B`, `C` or `Program`")
+                        .into())
+                }
             }
         }
 
@@ -110,3 +124,23 @@ impl FromStr for ThreeBitComputer {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn programs_are_three_bit() {
+        let error = Day17::parse("Register A: 0\n\nProgram: 2,4,9,0")
+            .unwrap_err()
+            .to_string();
+        assert_eq!(
+            error,
+            "line 3, column 14: expected a 3-bit value, 0 to 7 at `9`"
+        );
+
+        // No value of register A makes the program print this.
+        let computer = Day17::parse("Register A: 0\n\nProgram: 7,0").unwrap();
+        assert!(Day17::part2(&computer).is_err());
+    }
+}
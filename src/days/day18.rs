@@ -1,22 +1,23 @@
-use std::{
-    collections::{BinaryHeap, HashMap},
-    str::FromStr,
-};
+use std::str::FromStr;
 
 use anyhow::{anyhow, Result};
 
-use crate::{solution::Solution, vector::Vec2, vector_map::VectorMap};
+use crate::{parse::Text, search, solution::Solution, vector::Vec2, vector_map::VectorMap};
 
 pub struct Day18;
 
 impl Solution for Day18 {
     type Answer = String;
+    type Parsed<'a> = MemoryMaze;
     fn day(&self) -> u8 {
         18
     }
 
-    fn part1(input: &str) -> Result<Self::Answer> {
-        let maze: MemoryMaze = input.parse()?;
+    fn parse(input: &str) -> Result<Self::Parsed<'_>> {
+        input.parse()
+    }
+
+    fn part1(maze: &MemoryMaze) -> Result<Self::Answer> {
         let take = if cfg!(test) { 12 } else { 1024 };
         let map = maze.to_vector_map(take);
 
@@ -25,8 +26,7 @@ impl Solution for Day18 {
             .map(|steps| steps.to_string())
     }
 
-    fn part2(input: &str) -> Result<Self::Answer> {
-        let maze: MemoryMaze = input.parse()?;
+    fn part2(maze: &MemoryMaze) -> Result<Self::Answer> {
         let initial_take = if cfg!(test) { 0 } else { 1024 };
         let mut map = maze.to_vector_map(initial_take);
         for take in initial_take..maze.bytes.len() {
@@ -40,15 +40,19 @@ impl Solution for Day18 {
     }
 }
 
-struct MemoryMaze {
-    bytes: Vec<Vec2>,
-    size: Vec2,
+/// The falling byte positions, in order, and the size of the memory space.
+#[derive(Debug)]
+pub struct MemoryMaze {
+    pub bytes: Vec<Vec2>,
+    pub size: Vec2,
 }
 
-struct MemoryMap(VectorMap<bool>);
+/// Memory space where `true` marks a corrupted position.
+pub struct MemoryMap(pub VectorMap<bool>);
 
 impl MemoryMaze {
-    fn to_vector_map(&self, take: usize) -> MemoryMap {
+    /// The memory space after the first `take` bytes have fallen.
+    pub fn to_vector_map(&self, take: usize) -> MemoryMap {
         let mut map = VectorMap::new(self.size, false);
 
         for pos in self.bytes.iter().take(take) {
@@ -60,51 +64,22 @@ impl MemoryMaze {
 }
 
 impl MemoryMap {
-    fn find_shortest_path(&self) -> Option<usize> {
+    /// Fewest steps from the top left to the bottom right corner, if they are connected.
+    pub fn find_shortest_path(&self) -> Option<usize> {
         let start = Vec2::new(0, 0);
         let end = self.0.size() - Vec2::new(1, 1);
 
-        let mut visited = HashMap::new();
-        let mut queue: BinaryHeap<Step> = BinaryHeap::new();
-
-        queue.push(Step(0, start));
-
-        while let Some(Step(steps, pos)) = queue.pop() {
-            if pos == end {
-                return Some(steps);
-            }
-
-            if let Some(&prev_steps) = visited.get(&pos) {
-                if prev_steps <= steps {
-                    continue;
-                }
-            }
-
-            visited.insert(pos, steps);
-
-            for neighbor in pos.get_surrounding_4() {
-                if let Some(false) = self.0.get(&neighbor) {
-                    queue.push(Step(steps + 1, neighbor));
-                }
-            }
-        }
-
-        None
-    }
-}
-
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-struct Step(usize, Vec2);
-
-impl PartialOrd for Step {
-    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        Some(self.cmp(other))
-    }
-}
-
-impl Ord for Step {
-    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        self.0.cmp(&other.0).reverse()
+        search::bfs(
+            [start],
+            |&pos| {
+                self.0
+                    .neighbors4(pos)
+                    .filter(|(_, &corrupted)| !corrupted)
+                    .map(|(neighbor, _)| neighbor)
+            },
+            |&pos| pos == end,
+        )
+        .map(|path| path.cost)
     }
 }
 
@@ -112,14 +87,29 @@ impl FromStr for MemoryMaze {
     type Err = anyhow::Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (size_str, bytes_str) = s.split_once("\n\n").unwrap();
-
-        let size = size_str.parse()?;
+        let text = Text::new(s);
+        let (size_str, bytes_str) = text.split_once(s, "\n\n")?;
+
+        let size: Vec2 = text.parse(size_str)?;
+        if size.x <= 0 || size.y <= 0 {
+            return Err(text
+                .error(size_str, "expected a size above 0 in both directions")
+                .into());
+        }
 
         let bytes = bytes_str
             .lines()
-            .map(|line| line.parse())
-            .collect::<Result<_>>()?;
+            .map(|line| {
+                let pos: Vec2 = text.parse(line)?;
+                if !pos.contained_in(Vec2::new(0, 0), size) {
+                    return Err(text.error(
+                        line,
+                        format!("byte outside the {}×{} memory space", size.x, size.y),
+                    ));
+                }
+                Ok(pos)
+            })
+            .collect::<Result<_, _>>()?;
 
         Ok(Self { bytes, size })
     }
@@ -138,4 +128,14 @@ mod tests {
     fn test_part2() {
         assert_eq!(Day18.run_test2(), "6,1");
     }
+
+    #[test]
+    fn malformed_input_is_located() {
+        let error = Day18::parse("7,7\n\n1,1\n100,100").unwrap_err().to_string();
+        assert!(error.starts_with("line 4, column 1:"), "{}", error);
+        assert!(error.contains("outside"), "{}", error);
+
+        let error = Day18::parse("-1,3\n\n1,1").unwrap_err().to_string();
+        assert!(error.starts_with("line 1, column 1:"), "{}", error);
+    }
 }
@@ -1,30 +1,33 @@
 use std::collections::HashMap;
 
-use anyhow::{anyhow, Result};
+use anyhow::Result;
 use rayon::prelude::*;
 
-use crate::solution::Solution;
+use crate::{parse::Text, solution::Solution};
 
 pub struct Day19;
 
 impl Solution for Day19 {
     type Answer = usize;
+    type Parsed<'a> = TowelRack<'a>;
     fn day(&self) -> u8 {
         19
     }
 
-    fn part1(input: &str) -> Result<usize> {
-        let rack = TowelRack::try_from(input)?;
+    fn parse(input: &str) -> Result<Self::Parsed<'_>> {
+        TowelRack::try_from(input)
+    }
+
+    fn part1(rack: &TowelRack) -> Result<usize> {
         Ok(rack.count_reachable_patterns())
     }
 
-    fn part2(input: &str) -> Result<usize> {
-        let rack = TowelRack::try_from(input)?;
+    fn part2(rack: &TowelRack) -> Result<usize> {
         Ok(rack.count_all_possible_patterns())
     }
 }
 
-struct TowelRack<'a> {
+pub struct TowelRack<'a> {
     available_sub_patterns: Vec<&'a str>,
     towels: Vec<&'a str>,
 }
@@ -81,9 +84,7 @@ impl<'a> TryFrom<&'a str> for TowelRack<'a> {
     type Error = anyhow::Error;
 
     fn try_from(input: &'a str) -> Result<Self> {
-        let (towels_str, patterns_str) = input
-            .split_once("\n\n")
-            .ok_or(anyhow!("Failed to split input string"))?;
+        let (towels_str, patterns_str) = Text::new(input).split_once(input, "\n\n")?;
 
         let towels = towels_str.split(", ").collect();
         let patterns = patterns_str.lines().collect();
@@ -7,30 +7,39 @@ use std::{
 use anyhow::{anyhow, Result};
 use rayon::prelude::*;
 
-use crate::{solution::Solution, vector::Vec2, vector_map::VectorMap};
+use crate::{
+    profile,
+    render::{Cell, Color, Overlay, Renderer},
+    solution::Solution,
+    vector::Vec2,
+    vector_map::VectorMap,
+};
 
 pub struct Day20;
 
 impl Solution for Day20 {
     type Answer = u64;
+    type Parsed<'a> = Track;
     fn day(&self) -> u8 {
         20
     }
 
-    fn part1(input: &str) -> Result<Self::Answer> {
-        let track = input.parse::<Track>()?;
+    fn parse(input: &str) -> Result<Self::Parsed<'_>> {
+        input.parse()
+    }
+
+    fn part1(track: &Track) -> Result<Self::Answer> {
         let threshold = if cfg!(test) { 10 } else { 100 };
         Ok(track.count_cheats_above_threshold(threshold, 2))
     }
 
-    fn part2(input: &str) -> Result<Self::Answer> {
-        let track = input.parse::<Track>()?;
+    fn part2(track: &Track) -> Result<Self::Answer> {
         let threshold = if cfg!(test) { 50 } else { 100 };
         Ok(track.count_cheats_above_threshold(threshold, 20))
     }
 }
 
-struct Track {
+pub struct Track {
     map: VectorMap<bool>,
     start: Vec2,
 }
@@ -53,8 +62,8 @@ impl Track {
             costs.set(&pos, current_cost);
             current_cost += 1;
 
-            for next_pos in pos.get_surrounding_4() {
-                if let Some(true) = self.map.get(&next_pos) {
+            for (next_pos, &open) in self.map.neighbors4(pos) {
+                if open {
                     queue.push_back(next_pos);
                 }
             }
@@ -64,33 +73,64 @@ impl Track {
     }
 
     fn count_cheats_above_threshold(&self, threshold: u64, max_distance: u64) -> u64 {
-        let costs = self.calculate_costs_map();
+        let costs = {
+            let _span = profile::span("cost map");
+            self.calculate_costs_map()
+        };
         let candidates: Vec<_> = costs
             .iter()
             .filter_map(|(pos, &cost)| if cost != u64::MAX { Some(pos) } else { None })
             .collect();
 
+        let _span = profile::span("cheat search");
         candidates
             .par_iter()
             .map(|&start| {
-                Self::find_cheats(&costs, start, max_distance)
-                    .into_iter()
-                    .filter(|&value| value >= threshold)
+                Self::cheats(&costs, start, max_distance)
+                    .filter(|&(_, value)| value >= threshold)
                     .count() as u64
             })
             .sum()
     }
 
-    fn find_cheats(costs: &VectorMap<u64>, start: Vec2, max_distance: u64) -> Vec<u64> {
-        let start_cost = *costs.get(&start).unwrap();
+    /// Where each cheat from `start` ends and how much time it saves.
+    fn cheats(
+        costs: &VectorMap<u64>,
+        start: Vec2,
+        max_distance: u64,
+    ) -> impl Iterator<Item = (Vec2, u64)> + '_ {
+        let start_cost = costs[start];
         costs
             .iter()
-            .filter(|(end, _)| start.manhattan_distance(end) <= max_distance)
+            .filter(move |(end, _)| start.manhattan_distance(end) <= max_distance)
             .filter(|(_, &cost)| cost != u64::MAX)
-            .filter(|(_, &end_cost)| end_cost > start_cost)
-            .map(|(end, &cost)| cost.abs_diff(start_cost) - start.manhattan_distance(&end))
-            .filter(|&value| value > 0)
-            .collect()
+            .filter(move |(_, &end_cost)| end_cost > start_cost)
+            .map(move |(end, &cost)| {
+                let saved = cost.abs_diff(start_cost) - start.manhattan_distance(&end);
+                (end, saved)
+            })
+            .filter(|&(_, value)| value > 0)
+    }
+
+    /// The track with the ends of the cheats from `from` that save at least `threshold` highlighted.
+    pub fn render_cheats(
+        &self,
+        from: Vec2,
+        max_distance: u64,
+        threshold: u64,
+    ) -> Renderer<'_, bool> {
+        let costs = self.calculate_costs_map();
+        let ends: Vec<_> = Self::cheats(&costs, from, max_distance)
+            .filter(|&(_, value)| value >= threshold)
+            .map(|(end, _)| end)
+            .collect();
+
+        Renderer::new(&self.map, |&open| match open {
+            true => Cell::new('.'),
+            false => Cell::colored('#', Color::GRAY),
+        })
+        .overlay(Overlay::color(ends, Color::YELLOW).glyph('*'))
+        .overlay(Overlay::color([from], Color::RED).glyph('@'))
     }
 }
 
@@ -98,33 +138,16 @@ impl FromStr for Track {
     type Err = anyhow::Error;
 
     fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
-        let width = s.lines().next().unwrap().len();
-        let height = s.lines().count();
-
-        let mut map = VectorMap::new((width, height).into(), false);
-
-        let mut start = None;
-        for (y, line) in s.lines().enumerate() {
-            for (x, c) in line.chars().enumerate() {
-                let pos = (x, y).into();
-                match c {
-                    '.' | 'E' => {
-                        map.set(&pos, true);
-                    }
-                    '#' => {
-                        map.set(&pos, false);
-                    }
-                    'S' => {
-                        start = Some(pos);
-                        map.set(&pos, true);
-                    }
-                    _ => return Err(anyhow!("Invalid character: {} in position {}", c, pos)),
-                }
-            }
-        }
-        let start = start.ok_or(anyhow!("No start found"))?;
-
-        Ok(Self { map, start })
+        let (map, markers) = VectorMap::parse_with(s, "S", |c, _| match c {
+            '.' | 'S' | 'E' => Ok(true),
+            '#' => Ok(false),
+            _ => Err(anyhow!("expected `.`, `#`, `S` or `E`")),
+        })?;
+
+        Ok(Self {
+            map,
+            start: markers.one('S')?,
+        })
     }
 }
 
@@ -186,9 +209,18 @@ mod tests {
 ###############"#;
         let track = input.parse::<Track>().unwrap();
         let costs = track.calculate_costs_map();
-        assert_eq!(Track::find_cheats(&costs, (1, 3).into(), 2), vec![4]);
-        assert_eq!(Track::find_cheats(&costs, (7, 1).into(), 2), vec![12]);
-        assert_eq!(Track::find_cheats(&costs, (9, 7).into(), 2), vec![20, 36]);
-        assert_eq!(Track::find_cheats(&costs, (8, 7).into(), 2), vec![38]);
+        let saved = |start: Vec2| -> Vec<u64> {
+            Track::cheats(&costs, start, 2)
+                .map(|(_, saved)| saved)
+                .collect()
+        };
+        assert_eq!(saved((1, 3).into()), vec![4]);
+        assert_eq!(saved((7, 1).into()), vec![12]);
+        assert_eq!(saved((9, 7).into()), vec![20, 36]);
+        assert_eq!(saved((8, 7).into()), vec![38]);
+
+        let cheats = track.render_cheats((9, 7).into(), 2, 30).text();
+        assert_eq!(cheats.lines().nth(7).unwrap(), "###...#..@#...#");
+        assert_eq!(cheats.lines().nth(9).unwrap(), "#...###..*#...#");
     }
 }
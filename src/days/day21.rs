@@ -5,40 +5,51 @@ use std::{
 
 use itertools::Itertools;
 
-use crate::{solution::Solution, vector::Vec2};
+use crate::{parse::Text, solution::Solution, vector::Vec2};
 
 pub struct Day21;
 
 impl Solution for Day21 {
     type Answer = usize;
+    /// Each code with its numeric part.
+    type Parsed<'a> = Vec<(&'a str, usize)>;
     fn day(&self) -> u8 {
         21
     }
 
-    fn part1(input: &str) -> anyhow::Result<Self::Answer> {
+    fn parse(input: &str) -> anyhow::Result<Self::Parsed<'_>> {
+        let text = Text::new(input);
+        let codes = text.lines().map(|line| {
+            if let Some(x) = line.find(|c: char| !c.is_ascii_digit() && c != 'A') {
+                return Err(text.char_error(line, x, "expected a digit or `A`"));
+            }
+            Ok((line, text.parse(line.trim_end_matches('A'))?))
+        });
+        Ok(codes.collect::<Result<_, _>>()?)
+    }
+
+    fn part1(codes: &Vec<(&str, usize)>) -> anyhow::Result<Self::Answer> {
         let np = Keypad::numeric();
         let dp = Keypad::directional();
         let mut cache = HashMap::new();
-        let result = input
-            .lines()
-            .map(|line| {
-                shortest_len(&np, &dp, String::from(line), 0, 2, &mut cache)
-                    * line[0..3].parse::<usize>().unwrap()
+        let result = codes
+            .iter()
+            .map(|&(line, value)| {
+                shortest_len(&np, &dp, String::from(line), 0, 2, &mut cache) * value
             })
             .sum();
 
         Ok(result)
     }
 
-    fn part2(input: &str) -> anyhow::Result<Self::Answer> {
+    fn part2(codes: &Vec<(&str, usize)>) -> anyhow::Result<Self::Answer> {
         let np = Keypad::numeric();
         let dp = Keypad::directional();
         let mut cache = HashMap::new();
-        let result = input
-            .lines()
-            .map(|line| {
-                shortest_len(&np, &dp, String::from(line), 0, 25, &mut cache)
-                    * line[0..3].parse::<usize>().unwrap()
+        let result = codes
+            .iter()
+            .map(|&(line, value)| {
+                shortest_len(&np, &dp, String::from(line), 0, 25, &mut cache) * value
             })
             .sum();
 
@@ -4,37 +4,34 @@ use anyhow::{anyhow, Result};
 use itertools::Itertools;
 use rayon::prelude::*;
 
-use crate::solution::Solution;
+use crate::{parse::Text, solution::Solution};
 
 pub struct Day22;
 
 impl Solution for Day22 {
     type Answer = u64;
+    type Parsed<'a> = Vec<u32>;
     fn day(&self) -> u8 {
         22
     }
 
-    fn part1(input: &str) -> Result<Self::Answer> {
-        let ns = input
-            .lines()
-            .map(|line| line.parse::<u32>().unwrap())
-            .collect::<Vec<_>>();
+    fn parse(input: &str) -> Result<Self::Parsed<'_>> {
+        Ok(Text::new(input).parse_lines()?)
+    }
 
+    fn part1(ns: &Vec<u32>) -> Result<Self::Answer> {
         Ok(ns
-            .into_iter()
+            .iter()
+            .copied()
             .map(PRNG::new)
             .map(|mut prng| prng.nth(1999).unwrap() as u64)
             .sum())
     }
 
-    fn part2(input: &str) -> Result<Self::Answer> {
-        let ns = input
-            .lines()
-            .map(|line| line.parse::<u32>().unwrap())
-            .collect::<Vec<_>>();
-
+    fn part2(ns: &Vec<u32>) -> Result<Self::Answer> {
         let secret_numbers = ns
-            .into_iter()
+            .iter()
+            .copied()
             .map(PRNG::new)
             .map(|prng| prng.take(2000).collect::<Vec<_>>());
 
@@ -144,7 +141,7 @@ mod tests {
 10
 100
 2024"#;
-        assert_eq!(Day22::part1(part1_sample)?, 37327623);
+        assert_eq!(Day22::part1(&Day22::parse(part1_sample)?)?, 37327623);
         Ok(())
     }
 
@@ -2,18 +2,22 @@ use std::collections::{HashMap, HashSet};
 
 use anyhow::Result;
 
-use crate::solution::Solution;
+use crate::{parse::Text, solution::Solution};
 
 pub struct Day23;
 
 impl Solution for Day23 {
     type Answer = String;
+    type Parsed<'a> = Network<'a>;
     fn day(&self) -> u8 {
         23
     }
 
-    fn part1(input: &str) -> Result<Self::Answer> {
-        let network = Network::try_from(input)?;
+    fn parse(input: &str) -> Result<Self::Parsed<'_>> {
+        Network::try_from(input)
+    }
+
+    fn part1(network: &Network) -> Result<Self::Answer> {
         let triangles = network.find_triangles();
         let triangles_with_starting_with_t = triangles
             .iter()
@@ -23,14 +27,13 @@ impl Solution for Day23 {
         Ok((triangles_with_starting_with_t / 6).to_string())
     }
 
-    fn part2(input: &str) -> Result<Self::Answer> {
-        let network = Network::try_from(input)?;
+    fn part2(network: &Network) -> Result<Self::Answer> {
         let largest_clique = network.find_largest_clique();
         Ok(clique_to_sorted_string(&largest_clique))
     }
 }
 
-struct Network<'a> {
+pub struct Network<'a> {
     edges: HashMap<&'a str, HashSet<&'a str>>,
 }
 
@@ -90,9 +93,10 @@ impl<'a> TryFrom<&'a str> for Network<'a> {
     type Error = anyhow::Error;
 
     fn try_from(s: &'a str) -> std::result::Result<Self, Self::Error> {
-        let lines = s.lines().filter_map(|line| line.split_once('-'));
+        let text = Text::new(s);
         let mut edges = HashMap::new();
-        for (from, to) in lines {
+        for line in s.lines().filter(|line| !line.is_empty()) {
+            let (from, to) = text.split_once(line, "-")?;
             edges.entry(from).or_insert_with(HashSet::new).insert(to);
             edges.entry(to).or_insert_with(HashSet::new).insert(from);
         }
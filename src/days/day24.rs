@@ -6,24 +6,28 @@ use std::{
 use anyhow::{anyhow, Result};
 use itertools::Itertools;
 
-use crate::solution::Solution;
+use crate::{parse::Text, solution::Solution};
 
 pub struct Day24;
 
 impl Solution for Day24 {
     type Answer = String;
+    type Parsed<'a> = Circuit;
     fn day(&self) -> u8 {
         24
     }
 
-    fn part1(input: &str) -> Result<Self::Answer> {
-        let mut circuit = input.parse::<Circuit>()?;
-        circuit.resolve_values();
-        Ok(circuit.read_z_value().to_string())
+    fn parse(input: &str) -> Result<Self::Parsed<'_>> {
+        input.parse()
     }
 
-    fn part2(input: &str) -> Result<Self::Answer> {
-        let circuit = input.parse::<Circuit>()?;
+    fn part1(circuit: &Circuit) -> Result<Self::Answer> {
+        let mut circuit = circuit.clone();
+        circuit.resolve_values()?;
+        Ok(circuit.read_z_value()?.to_string())
+    }
+
+    fn part2(circuit: &Circuit) -> Result<Self::Answer> {
         let bit_length = (circuit.values.len() / 2) as u8;
         let mut swapped = HashSet::new();
 
@@ -38,7 +42,7 @@ impl Solution for Day24 {
                 }
                 false
             })
-            .unwrap();
+            .ok_or(anyhow!("No XOR gate adds x00 and y00"))?;
 
         if z00.output() != "z00" {
             swapped.insert(z00.output().clone());
@@ -55,7 +59,7 @@ impl Solution for Day24 {
                 }
                 None
             })
-            .unwrap();
+            .ok_or(anyhow!("No AND gate carries x00 and y00"))?;
 
         for bit in 1..bit_length {
             let x = format!("x{:02}", bit);
@@ -73,7 +77,7 @@ impl Solution for Day24 {
                     }
                     false
                 })
-                .unwrap()
+                .ok_or_else(|| anyhow!("No XOR gate adds {} and {}", x, y))?
                 .output();
 
             let add = circuit
@@ -87,7 +91,7 @@ impl Solution for Day24 {
                     }
                     false
                 })
-                .unwrap();
+                .ok_or_else(|| anyhow!("No XOR gate adds the carry into z{:02}", bit))?;
 
             if add.output() != z {
                 swapped.insert(z);
@@ -113,7 +117,7 @@ impl Solution for Day24 {
                     }
                     false
                 })
-                .unwrap()
+                .ok_or_else(|| anyhow!("No AND gate carries {} and {}", x, y))?
                 .output();
 
             let cascade_carry = circuit
@@ -127,7 +131,7 @@ impl Solution for Day24 {
                     }
                     false
                 })
-                .unwrap();
+                .ok_or_else(|| anyhow!("No AND gate carries the carry into z{:02}", bit))?;
 
             if !cascade_carry.is_input(&basic_add) {
                 swapped.insert(basic_add.clone());
@@ -148,7 +152,7 @@ impl Solution for Day24 {
                     }
                     false
                 })
-                .unwrap();
+                .ok_or_else(|| anyhow!("No OR gate combines the carries out of z{:02}", bit))?;
 
             if !carry_gate.is_input(&basic_carry) {
                 swapped.insert(basic_carry);
@@ -167,19 +171,21 @@ impl Solution for Day24 {
     }
 }
 
+#[derive(Clone, Debug)]
 enum Gate {
     And(String, String, String),
     Or(String, String, String),
     Xor(String, String, String),
 }
 
-struct Circuit {
+#[derive(Clone, Debug)]
+pub struct Circuit {
     values: HashMap<String, bool>,
     gates: HashMap<String, Gate>,
 }
 
 impl Circuit {
-    fn resolve_values(&mut self) {
+    fn resolve_values(&mut self) -> Result<()> {
         let mut unresolved = HashSet::new();
         unresolved.extend(self.gates.keys());
         for key in self.values.keys() {
@@ -189,7 +195,7 @@ impl Circuit {
         while !unresolved.is_empty() {
             let mut resolved = HashSet::new();
             for &key in unresolved.iter() {
-                let gate = self.gates.get(key).unwrap();
+                let gate = &self.gates[key];
                 let input1 = &gate.in1();
                 let input2 = &gate.in2();
                 let output = &gate.output();
@@ -207,21 +213,32 @@ impl Circuit {
                 }
             }
 
+            if resolved.is_empty() {
+                return Err(anyhow!(
+                    "The gates for {} feed into each other",
+                    unresolved.iter().sorted().join(", ")
+                ));
+            }
             for key in resolved {
                 unresolved.remove(&key);
             }
         }
+        Ok(())
     }
 
-    fn read_z_value(&self) -> u64 {
+    fn read_z_value(&self) -> Result<u64> {
         let mut z_value = 0;
         for (key, value) in self.values.iter().filter(|(key, _)| key.starts_with("z")) {
-            let index = key[1..].parse::<u64>().unwrap();
+            let index = key[1..]
+                .parse::<u32>()
+                .ok()
+                .filter(|&index| index < u64::BITS)
+                .ok_or_else(|| anyhow!("Expected an output bit from z00 to z63, found {}", key))?;
             if *value {
                 z_value |= 1 << index;
             }
         }
-        z_value
+        Ok(z_value)
     }
 }
 
@@ -229,26 +246,41 @@ impl FromStr for Circuit {
     type Err = anyhow::Error;
 
     fn from_str(s: &str) -> Result<Self> {
-        let (values_str, gates_str) = s.split_once("\n\n").ok_or(anyhow!("Invalid input"))?;
-        let values = values_str
+        let text = Text::new(s);
+        let (values_str, gates_str) = text.split_once(s, "\n\n")?;
+        let values: HashMap<String, bool> = values_str
             .lines()
             .map(|line| {
-                let (key, value) = line.split_once(": ").ok_or(anyhow!("Invalid input"))?;
+                let (key, value) = text.split_once(line, ": ")?;
                 let val = match value {
                     "1" => true,
                     "0" => false,
-                    _ => return Err(anyhow!("Invalid input")),
+                    _ => return Err(text.error(value, "expected `0` or `1`")),
                 };
 
                 Ok((key.to_string(), val))
             })
-            .collect::<Result<_>>()?;
+            .collect::<Result<_, _>>()?;
 
-        let gates = gates_str
+        let gates: HashMap<String, Gate> = gates_str
             .lines()
-            .map(|line| line.parse::<Gate>())
+            .map(|line| text.parse::<Gate>(line))
             .map(|gate| gate.map(|gate| (gate.output(), gate)))
-            .collect::<Result<_>>()?;
+            .collect::<Result<_, _>>()?;
+
+        // Every input has to be set at the start or come out of another gate.
+        for line in gates_str.lines() {
+            for wire in line.split_whitespace().step_by(2).take(2) {
+                if !values.contains_key(wire) && !gates.contains_key(wire) {
+                    return Err(text
+                        .error(
+                            wire,
+                            format!("`{}` is neither set nor a gate's output", wire),
+                        )
+                        .into());
+                }
+            }
+        }
 
         Ok(Circuit { values, gates })
     }
@@ -258,16 +290,27 @@ impl FromStr for Gate {
     type Err = anyhow::Error;
 
     fn from_str(s: &str) -> Result<Self> {
-        let parts: Vec<&str> = s.split_whitespace().collect();
-        let input1 = String::from(parts[0]);
-        let input2 = String::from(parts[2]);
-        let output = String::from(parts[4]);
-
-        match parts[1] {
+        let text = Text::new(s);
+        let (inputs, output) = text.split_once(s, " -> ")?;
+        let parts: Vec<&str> = inputs.split_whitespace().collect();
+        let &[input1, operation, input2] = parts.as_slice() else {
+            return Err(text
+                .error(inputs, "expected `<wire> AND|OR|XOR <wire>`")
+                .into());
+        };
+        let (input1, input2, output) = (
+            String::from(input1),
+            String::from(input2),
+            String::from(output.trim()),
+        );
+
+        match operation {
             "AND" => Ok(Gate::And(input1, input2, output)),
             "OR" => Ok(Gate::Or(input1, input2, output)),
             "XOR" => Ok(Gate::Xor(input1, input2, output)),
-            _ => Err(anyhow!("Invalid gate")),
+            _ => Err(text
+                .error(operation, "expected `AND`, `OR` or `XOR`")
+                .into()),
         }
     }
 }
@@ -320,4 +363,23 @@ mod tests {
     fn test_part2() {
         assert_eq!(Day24.run_test2(), "0");
     }
+
+    #[test]
+    fn undefined_wires_are_located() {
+        let error = Day24::parse("x00: 1\n\nx00 AND y00 -> z00")
+            .unwrap_err()
+            .to_string();
+        assert_eq!(
+            error,
+            "line 3, column 9: `y00` is neither set nor a gate's output at `y00`"
+        );
+    }
+
+    #[test]
+    fn loops_are_errors() {
+        let circuit = Day24::parse("x00: 1\n\nx00 AND z01 -> z00\nx00 OR z00 -> z01").unwrap();
+        let error = Day24::part1(&circuit).unwrap_err().to_string();
+        assert!(error.contains("z00, z01"), "{}", error);
+        assert!(Day24::part2(&circuit).is_err());
+    }
 }
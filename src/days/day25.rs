@@ -2,27 +2,34 @@ use std::str::FromStr;
 
 use anyhow::Result;
 
-use crate::solution::Solution;
+use crate::{
+    parse::{ParseError, Text},
+    solution::Solution,
+};
 
 pub struct Day25;
 
 impl Solution for Day25 {
     type Answer = u64;
+    type Parsed<'a> = Schematics;
     fn day(&self) -> u8 {
         25
     }
 
-    fn part1(input: &str) -> Result<Self::Answer> {
-        let schematics = Schematics::from_str(input)?;
+    fn parse(input: &str) -> Result<Self::Parsed<'_>> {
+        Schematics::from_str(input)
+    }
+
+    fn part1(schematics: &Schematics) -> Result<Self::Answer> {
         Ok(schematics.count_matching_keys())
     }
 
-    fn part2(_: &str) -> Result<Self::Answer> {
+    fn part2(_: &Schematics) -> Result<Self::Answer> {
         Ok(1337)
     }
 }
 
-struct Schematics {
+pub struct Schematics {
     keys: Vec<Key>,
     locks: Vec<Lock>,
 }
@@ -45,14 +52,20 @@ impl FromStr for Schematics {
     type Err = anyhow::Error;
 
     fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        let text = Text::new(s);
         let mut keys = Vec::new();
         let mut locks = Vec::new();
 
-        for block in s.split("\n\n") {
+        for block in text.sections() {
             if block.starts_with(".....") {
-                keys.push(Key::from_str(block)?);
+                keys.push(text.parse(block)?);
             } else if block.starts_with("#####") {
-                locks.push(Lock::from_str(block)?);
+                locks.push(text.parse(block)?);
+            } else {
+                let first_line = block.lines().next().unwrap_or(block);
+                return Err(text
+                    .error(first_line, "expected a key `.....` or a lock `#####`")
+                    .into());
             }
         }
 
@@ -92,13 +105,7 @@ impl FromStr for Key {
     type Err = anyhow::Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        if s.lines().next().unwrap() != "....." {
-            return Err(anyhow::anyhow!("Invalid key"));
-        }
-        let mut schematic_block = s
-            .lines()
-            .map(|l| l.chars().collect())
-            .collect::<Vec<Vec<_>>>();
+        let mut schematic_block = schematic_rows(s, ".....", "#####")?;
         schematic_block.reverse();
 
         Ok(Key::new(parse_block(schematic_block)))
@@ -109,18 +116,31 @@ impl FromStr for Lock {
     type Err = anyhow::Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        if s.lines().next().unwrap() != "#####" {
-            return Err(anyhow::anyhow!("Invalid lock"));
-        }
-        let schematic_block = s
-            .lines()
-            .map(|l| l.chars().collect())
-            .collect::<Vec<Vec<_>>>();
+        let schematic_block = schematic_rows(s, "#####", ".....")?;
 
         Ok(Lock::new(parse_block(schematic_block)))
     }
 }
 
+/// The rows of a key or lock, which have to start with `top` and end with `bottom`.
+fn schematic_rows(s: &str, top: &str, bottom: &str) -> Result<Vec<Vec<char>>, ParseError> {
+    let text = Text::new(s);
+    let rows = text.grid()?;
+    let (first, last) = (rows[0], rows[rows.len() - 1]);
+    if first != top {
+        return Err(text.error(first, format!("expected `{}`", top)));
+    }
+    if last != bottom {
+        return Err(text.error(last, format!("expected `{}`", bottom)));
+    }
+    for row in &rows {
+        if let Some(x) = row.find(|c| c != '#' && c != '.') {
+            return Err(text.char_error(row, x, "expected `#` or `.`"));
+        }
+    }
+    Ok(rows.iter().map(|row| row.chars().collect()).collect())
+}
+
 fn parse_block(block: Vec<Vec<char>>) -> (u8, u8, u8, u8, u8) {
     let rows = transpose(block);
     // for each row, count the number of #s
@@ -1,28 +1,30 @@
-mod day01;
-mod day02;
-mod day03;
-mod day04;
-mod day05;
-mod day06;
-mod day07;
-mod day08;
-mod day09;
-mod day10;
-mod day11;
-mod day12;
-mod day13;
-mod day14;
-mod day15;
-mod day16;
-mod day17;
-mod day18;
-mod day19;
-mod day20;
-mod day21;
-mod day22;
-mod day23;
-mod day24;
-mod day25;
+use crate::registry::Registry;
+
+pub mod day01;
+pub mod day02;
+pub mod day03;
+pub mod day04;
+pub mod day05;
+pub mod day06;
+pub mod day07;
+pub mod day08;
+pub mod day09;
+pub mod day10;
+pub mod day11;
+pub mod day12;
+pub mod day13;
+pub mod day14;
+pub mod day15;
+pub mod day16;
+pub mod day17;
+pub mod day18;
+pub mod day19;
+pub mod day20;
+pub mod day21;
+pub mod day22;
+pub mod day23;
+pub mod day24;
+pub mod day25;
 
 pub use day01::Day01;
 pub use day02::Day02;
@@ -49,3 +51,32 @@ pub use day22::Day22;
 pub use day23::Day23;
 pub use day24::Day24;
 pub use day25::Day25;
+
+/// Registers the 2024 solutions.
+pub fn register(registry: &mut Registry) {
+    registry.register(Day01);
+    registry.register(Day02);
+    registry.register(Day03);
+    registry.register(Day04);
+    registry.register(Day05);
+    registry.register(Day06);
+    registry.register(Day07);
+    registry.register(Day08);
+    registry.register(Day09);
+    registry.register(Day10);
+    registry.register(Day11);
+    registry.register(Day12);
+    registry.register(Day13);
+    registry.register(Day14);
+    registry.register(Day15);
+    registry.register(Day16);
+    registry.register(Day17);
+    registry.register(Day18);
+    registry.register(Day19);
+    registry.register(Day20);
+    registry.register(Day21);
+    registry.register(Day22);
+    registry.register(Day23);
+    registry.register(Day24);
+    registry.register(Day25);
+}
@@ -0,0 +1,318 @@
+//! A minimal animated GIF encoder: one fixed 256 color palette, full frames, looping forever.
+
+use std::{collections::HashMap, io::Write};
+
+use anyhow::{anyhow, Result};
+
+use crate::render::Color;
+
+/// Color components of the 6×6×6 color cube that fills the first 216 palette entries.
+const CUBE_LEVELS: [u8; 6] = [0, 51, 102, 153, 204, 255];
+/// The rest of the palette, shades of gray.
+const GRAYS: usize = 40;
+
+const MIN_CODE_SIZE: u8 = 8;
+const MAX_CODE: u16 = 4095;
+
+/// The palette every image is drawn with.
+pub fn palette() -> Vec<Color> {
+    let cube = CUBE_LEVELS.iter().flat_map(|&r| {
+        CUBE_LEVELS
+            .iter()
+            .flat_map(move |&g| CUBE_LEVELS.iter().map(move |&b| Color::rgb(r, g, b)))
+    });
+    let grays = (0..GRAYS).map(|i| {
+        let level = gray_level(i);
+        Color::rgb(level, level, level)
+    });
+    cube.chain(grays).collect()
+}
+
+fn gray_level(index: usize) -> u8 {
+    ((index + 1) * 255 / (GRAYS + 1)) as u8
+}
+
+/// The index of the palette color closest to `color`.
+pub fn palette_index(color: Color) -> u8 {
+    let level = |component: u8| (component as usize * 5 + 127) / 255;
+    let (r, g, b) = (level(color.r), level(color.g), level(color.b));
+    let cube = Color::rgb(CUBE_LEVELS[r], CUBE_LEVELS[g], CUBE_LEVELS[b]);
+
+    let gray = (color.luma() as usize * (GRAYS + 1) + 127) / 255;
+    let gray = gray.clamp(1, GRAYS) - 1;
+    let level = gray_level(gray);
+
+    let distance = |other: Color| {
+        [(color.r, other.r), (color.g, other.g), (color.b, other.b)]
+            .into_iter()
+            .map(|(a, b)| (a as i32 - b as i32).pow(2))
+            .sum::<i32>()
+    };
+    if distance(Color::rgb(level, level, level)) < distance(cube) {
+        (216 + gray) as u8
+    } else {
+        (r * 36 + g * 6 + b) as u8
+    }
+}
+
+/// Writes an animated GIF frame by frame, so long animations never have to be held in memory.
+pub struct GifEncoder<W: Write> {
+    writer: W,
+    width: u16,
+    height: u16,
+}
+
+impl<W: Write> GifEncoder<W> {
+    /// Writes the header of a `width` × `height` animation that loops forever.
+    pub fn new(mut writer: W, width: usize, height: usize) -> Result<Self> {
+        let dimension = |value: usize| {
+            u16::try_from(value)
+                .ok()
+                .filter(|&value| value > 0)
+                .ok_or(anyhow!("Invalid GIF size {}×{}", width, height))
+        };
+        let (width, height) = (dimension(width)?, dimension(height)?);
+
+        writer.write_all(b"GIF89a")?;
+        writer.write_all(&width.to_le_bytes())?;
+        writer.write_all(&height.to_le_bytes())?;
+        // Global color table of 256 entries, 8 bits per color.
+        writer.write_all(&[0xf7, 0, 0])?;
+        for color in palette() {
+            writer.write_all(&[color.r, color.g, color.b])?;
+        }
+
+        // Loop forever.
+        writer.write_all(&[0x21, 0xff, 0x0b])?;
+        writer.write_all(b"NETSCAPE2.0")?;
+        writer.write_all(&[0x03, 0x01, 0x00, 0x00, 0x00])?;
+
+        Ok(GifEncoder {
+            writer,
+            width,
+            height,
+        })
+    }
+
+    /// Adds a frame of palette indices, row by row, shown for `delay` hundredths of a second.
+    pub fn add_frame(&mut self, pixels: &[u8], delay: u16) -> Result<()> {
+        if pixels.len() != self.width as usize * self.height as usize {
+            return Err(anyhow!(
+                "Expected a frame of {}×{} pixels, got {}",
+                self.width,
+                self.height,
+                pixels.len()
+            ));
+        }
+
+        self.writer.write_all(&[0x21, 0xf9, 0x04, 0x00])?;
+        self.writer.write_all(&delay.to_le_bytes())?;
+        self.writer.write_all(&[0x00, 0x00])?;
+
+        self.writer.write_all(&[0x2c, 0, 0, 0, 0])?;
+        self.writer.write_all(&self.width.to_le_bytes())?;
+        self.writer.write_all(&self.height.to_le_bytes())?;
+        self.writer.write_all(&[0x00, MIN_CODE_SIZE])?;
+
+        for block in lzw_encode(pixels).chunks(255) {
+            self.writer.write_all(&[block.len() as u8])?;
+            self.writer.write_all(block)?;
+        }
+        self.writer.write_all(&[0x00])?;
+        Ok(())
+    }
+
+    /// Writes the trailer and hands the writer back.
+    pub fn finish(mut self) -> Result<W> {
+        self.writer.write_all(&[0x3b])?;
+        self.writer.flush()?;
+        Ok(self.writer)
+    }
+}
+
+/// Packs codes of varying widths into bytes, least significant bit first.
+#[derive(Default)]
+struct BitWriter {
+    bytes: Vec<u8>,
+    buffer: u32,
+    bits: u8,
+}
+
+impl BitWriter {
+    fn write(&mut self, code: u16, size: u8) {
+        self.buffer |= (code as u32) << self.bits;
+        self.bits += size;
+        while self.bits >= 8 {
+            self.bytes.push(self.buffer as u8);
+            self.buffer >>= 8;
+            self.bits -= 8;
+        }
+    }
+
+    fn finish(mut self) -> Vec<u8> {
+        if self.bits > 0 {
+            self.bytes.push(self.buffer as u8);
+        }
+        self.bytes
+    }
+}
+
+/// GIF's variable-width LZW compression of 8 bit pixels.
+fn lzw_encode(pixels: &[u8]) -> Vec<u8> {
+    let clear = 1u16 << MIN_CODE_SIZE;
+    let end = clear + 1;
+
+    let mut output = BitWriter::default();
+    let mut codes: HashMap<(u16, u8), u16> = HashMap::new();
+    let mut next_code = end + 1;
+    let mut size = MIN_CODE_SIZE + 1;
+    output.write(clear, size);
+
+    let Some((&first, rest)) = pixels.split_first() else {
+        output.write(end, size);
+        return output.finish();
+    };
+
+    let mut prefix = first as u16;
+    for &pixel in rest {
+        if let Some(&code) = codes.get(&(prefix, pixel)) {
+            prefix = code;
+            continue;
+        }
+
+        output.write(prefix, size);
+        if next_code == MAX_CODE {
+            output.write(clear, size);
+            codes.clear();
+            next_code = end + 1;
+            size = MIN_CODE_SIZE + 1;
+        } else {
+            codes.insert((prefix, pixel), next_code);
+            next_code += 1;
+            if next_code > 1 << size {
+                size += 1;
+            }
+        }
+        prefix = pixel as u16;
+    }
+
+    output.write(prefix, size);
+    output.write(end, size);
+    output.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A straightforward decoder following the GIF spec, to check the encoder against.
+    fn lzw_decode(data: &[u8]) -> Vec<u8> {
+        let clear = 1usize << MIN_CODE_SIZE;
+        let end = clear + 1;
+        let reset = || -> Vec<Vec<u8>> {
+            (0..clear)
+                .map(|i| vec![i as u8])
+                .chain([vec![], vec![]])
+                .collect()
+        };
+
+        let mut table = reset();
+        let mut size = MIN_CODE_SIZE + 1;
+        let mut previous: Option<usize> = None;
+        let mut output = Vec::new();
+        let (mut buffer, mut bits, mut bytes) = (0u32, 0u8, data.iter());
+
+        loop {
+            while bits < size {
+                buffer |= (*bytes.next().unwrap() as u32) << bits;
+                bits += 8;
+            }
+            let code = (buffer & ((1 << size) - 1)) as usize;
+            buffer >>= size;
+            bits -= size;
+
+            if code == clear {
+                table = reset();
+                size = MIN_CODE_SIZE + 1;
+                previous = None;
+                continue;
+            }
+            if code == end {
+                return output;
+            }
+
+            let entry = match previous {
+                None => table[code].clone(),
+                Some(previous) => {
+                    let entry = if code < table.len() {
+                        table[code].clone()
+                    } else {
+                        let mut entry = table[previous].clone();
+                        entry.push(entry[0]);
+                        entry
+                    };
+                    let mut added = table[previous].clone();
+                    added.push(entry[0]);
+                    table.push(added);
+                    entry
+                }
+            };
+            output.extend_from_slice(&entry);
+            if table.len() == 1 << size && size < 12 {
+                size += 1;
+            }
+            previous = Some(code);
+        }
+    }
+
+    #[test]
+    fn lzw_round_trips() {
+        let inputs: Vec<Vec<u8>> = vec![
+            vec![],
+            vec![7],
+            vec![1, 1, 1, 1, 1, 1, 1, 1],
+            (0..=255).collect(),
+            // Enough varied data to fill the code table and force a clear code.
+            (0..200_000u32)
+                .map(|i| (i.wrapping_mul(2_654_435_761) >> 13) as u8 % 37)
+                .collect(),
+        ];
+        for input in inputs {
+            assert_eq!(lzw_decode(&lzw_encode(&input)), input);
+        }
+    }
+
+    #[test]
+    fn palette_lookup() {
+        let palette = palette();
+        assert_eq!(palette.len(), 256);
+        assert_eq!(palette[palette_index(Color::WHITE) as usize], Color::WHITE);
+        assert_eq!(palette[palette_index(Color::BLACK) as usize], Color::BLACK);
+        let gray = palette[palette_index(Color::GRAY) as usize];
+        assert!(gray.r.abs_diff(128) < 8 && gray.r == gray.g && gray.g == gray.b);
+
+        // Every palette color maps back to itself.
+        for (index, &color) in palette.iter().enumerate() {
+            assert_eq!(palette_index(color) as usize, index);
+        }
+    }
+
+    #[test]
+    fn writes_a_well_formed_animation() {
+        let mut encoder = GifEncoder::new(Vec::new(), 2, 2).unwrap();
+        encoder.add_frame(&[0, 1, 2, 3], 10).unwrap();
+        encoder.add_frame(&[3, 2, 1, 0], 10).unwrap();
+        assert!(encoder.add_frame(&[0], 10).is_err());
+        let gif = encoder.finish().unwrap();
+
+        assert_eq!(&gif[..10], b"GIF89a\x02\x00\x02\x00");
+        assert_eq!(gif.last(), Some(&0x3b));
+        assert_eq!(
+            gif.windows(3)
+                .filter(|window| window == &[0x21, 0xf9, 0x04])
+                .count(),
+            2
+        );
+        assert!(GifEncoder::new(Vec::new(), 0, 2).is_err());
+    }
+}
@@ -0,0 +1,171 @@
+use std::{
+    env,
+    fmt::{Display, Formatter},
+    fs,
+    io::{self, Read},
+    path::{Path, PathBuf},
+};
+
+use anyhow::{Context, Result};
+
+use crate::solution::DEFAULT_YEAR;
+
+pub const INPUT_DIR_VAR: &str = "AOC_INPUT_DIR";
+const DEFAULT_INPUT_DIR: &str = "./src/input";
+/// Where the checked-in `sampleNN*.txt` example inputs live.
+pub const SAMPLE_DIR: &str = "./src/input";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InputSource {
+    File(PathBuf),
+    Stdin,
+}
+
+impl InputSource {
+    /// Parses a `--input` argument, where `-` stands for stdin.
+    pub fn from_arg(arg: &str) -> Self {
+        match arg {
+            "-" => InputSource::Stdin,
+            path => InputSource::File(PathBuf::from(path)),
+        }
+    }
+
+    /// The default input file for a day, inside `$AOC_INPUT_DIR` or `./src/input`.
+    pub fn for_day(year: u16, day: u8) -> Self {
+        InputSource::File(day_path(&input_dir(), year, day))
+    }
+
+    pub fn read(&self) -> Result<String> {
+        match self {
+            InputSource::File(path) => fs::read_to_string(path)
+                .with_context(|| format!("Failed to read input from {}", path.display())),
+            InputSource::Stdin => {
+                let mut input = String::new();
+                io::stdin()
+                    .read_to_string(&mut input)
+                    .context("Failed to read input from stdin")?;
+                Ok(input)
+            }
+        }
+    }
+}
+
+impl Display for InputSource {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InputSource::File(path) => write!(f, "{}", path.display()),
+            InputSource::Stdin => write!(f, "<stdin>"),
+        }
+    }
+}
+
+/// The directory holding inputs (and the answers file): `$AOC_INPUT_DIR` or `./src/input`.
+pub fn input_dir() -> PathBuf {
+    env::var_os(INPUT_DIR_VAR)
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from(DEFAULT_INPUT_DIR))
+}
+
+/// `dir/YYYY/name`, or `dir/name` for a file of the default year that predates
+/// year directories.
+pub fn year_path(dir: &Path, year: u16, name: &str) -> PathBuf {
+    let path = dir.join(year.to_string()).join(name);
+    let legacy_path = dir.join(name);
+    if year == DEFAULT_YEAR && !path.exists() && legacy_path.exists() {
+        legacy_path
+    } else {
+        path
+    }
+}
+
+/// Where the samples of a year live: [`SAMPLE_DIR`] itself for the default year,
+/// a `YYYY` directory inside it otherwise.
+pub fn sample_dir(year: u16) -> PathBuf {
+    if year == DEFAULT_YEAR {
+        PathBuf::from(SAMPLE_DIR)
+    } else {
+        Path::new(SAMPLE_DIR).join(year.to_string())
+    }
+}
+
+pub fn sample_path(year: u16, day: u8) -> PathBuf {
+    sample_dir(year).join(format!("sample{:02}.txt", day))
+}
+
+/// The example inputs for a day: `sampleNN.txt` plus any `sampleNN<suffix>.txt` variants.
+pub fn sample_paths(year: u16, day: u8) -> Vec<PathBuf> {
+    let prefix = format!("sample{:02}", day);
+    let Ok(entries) = fs::read_dir(sample_dir(year)) else {
+        return Vec::new();
+    };
+    let mut paths: Vec<_> = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| {
+            let name = path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .unwrap_or("");
+            name.starts_with(&prefix) && name.ends_with(".txt")
+        })
+        .collect();
+    paths.sort();
+    paths
+}
+
+pub(crate) fn day_path(dir: &Path, year: u16, day: u8) -> PathBuf {
+    year_path(dir, year, &format!("day{:02}.txt", day))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_input_argument() {
+        assert_eq!(InputSource::from_arg("-"), InputSource::Stdin);
+        assert_eq!(
+            InputSource::from_arg("inputs/day01.txt"),
+            InputSource::File(PathBuf::from("inputs/day01.txt"))
+        );
+    }
+
+    #[test]
+    fn day_path_is_year_qualified() {
+        assert_eq!(
+            day_path(Path::new("/tmp/aoc"), 2023, 7),
+            PathBuf::from("/tmp/aoc/2023/day07.txt")
+        );
+    }
+
+    #[test]
+    fn default_year_falls_back_to_flat_layout() {
+        let dir = env::temp_dir().join(format!("aoc2024-layout-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(dir.join("2024")).unwrap();
+        fs::write(dir.join("day01.txt"), "flat").unwrap();
+        fs::write(dir.join("day02.txt"), "flat").unwrap();
+        fs::write(dir.join("2024/day02.txt"), "qualified").unwrap();
+
+        assert_eq!(day_path(&dir, 2024, 1), dir.join("day01.txt"));
+        assert_eq!(day_path(&dir, 2024, 2), dir.join("2024/day02.txt"));
+        assert_eq!(day_path(&dir, 2024, 3), dir.join("2024/day03.txt"));
+        assert_eq!(day_path(&dir, 2023, 1), dir.join("2023/day01.txt"));
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn finds_every_sample_of_a_day() {
+        let names: Vec<_> = sample_paths(DEFAULT_YEAR, 15)
+            .iter()
+            .map(|path| path.file_name().unwrap().to_string_lossy().into_owned())
+            .collect();
+        assert_eq!(names, vec!["sample15.txt", "sample15b.txt"]);
+    }
+
+    #[test]
+    fn missing_file_reports_path() {
+        let source = InputSource::File(PathBuf::from("./src/input/missing.txt"));
+        let error = source.read().unwrap_err();
+        assert!(error.to_string().contains("./src/input/missing.txt"));
+    }
+}
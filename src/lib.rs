@@ -0,0 +1,39 @@
+//! Advent of Code solutions and the utilities they are built on.
+//!
+//! Every day implements [`solution::Solution`] and is available from [`registry()`];
+//! grid helpers live in [`vector`], [`vector_map`] and [`render`], graph searches in
+//! [`search`]. Simulation days can be watched step by step through [`animate`]. The 2024 days
+//! live in [`days`], other years get a `yearYYYY` module of their own.
+
+pub mod days;
+
+pub mod animate;
+pub mod answers;
+pub mod bench;
+pub mod gif;
+pub mod input;
+pub mod memory;
+pub mod parse;
+pub mod profile;
+pub mod provider;
+pub mod registry;
+pub mod render;
+pub mod report;
+pub mod scaffold;
+pub mod search;
+pub mod solution;
+pub mod summary;
+pub mod threads;
+pub mod vector;
+pub mod vector_map;
+pub mod watch;
+
+#[cfg(test)]
+mod samples;
+
+/// Every solution of every year.
+pub fn registry() -> registry::Registry {
+    let mut registry = registry::Registry::default();
+    days::register(&mut registry);
+    registry
+}
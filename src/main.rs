@@ -1,45 +1,248 @@
-mod days;
-
-mod solution;
-mod vector;
-mod vector_map;
-
 mod cli;
 
+use std::{
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
 use anyhow::{anyhow, Result};
 use clap::Parser;
-use solution::Solution;
+use itertools::Itertools;
+
+use aoc2024::{
+    animate,
+    answers::Answers,
+    bench,
+    input::{self, InputSource},
+    profile,
+    provider::{self, Fetched, FileCache, HttpProvider},
+    registry::{DynSolution, Registry},
+    report, scaffold,
+    solution::{Parts, RunOptions},
+    threads, watch,
+};
 
-use days::*;
+const WATCH_INTERVAL: Duration = Duration::from_millis(250);
 
 fn main() -> Result<()> {
     let cli = cli::Cli::parse();
-    match cli.day {
-        1 => Day01.run(),
-        2 => Day02.run(),
-        3 => Day03.run(),
-        4 => Day04.run(),
-        5 => Day05.run(),
-        6 => Day06.run(),
-        7 => Day07.run(),
-        8 => Day08.run(),
-        9 => Day09.run(),
-        10 => Day10.run(),
-        11 => Day11.run(),
-        12 => Day12.run(),
-        13 => Day13.run(),
-        14 => Day14.run(),
-        15 => Day15.run(),
-        16 => Day16.run(),
-        17 => Day17.run(),
-        18 => Day18.run(),
-        19 => Day19.run(),
-        20 => Day20.run(),
-        21 => Day21.run(),
-        22 => Day22.run(),
-        23 => Day23.run(),
-        24 => Day24.run(),
-        25 => Day25.run(),
-        n => Err(anyhow!("Day {} not implemented", n)),
+    let registry = aoc2024::registry();
+
+    if let Some(command) = &cli.command {
+        return match command {
+            cli::Command::Fetch { day, year } => fetch(*year, *day),
+            cli::Command::New { day, year } => new_day(*year, *day),
+            cli::Command::Watch {
+                day,
+                year,
+                input,
+                part,
+            } => watch(&registry, *year, *day, input.clone(), *part),
+        };
+    }
+
+    if let Some(threads) = cli.threads {
+        threads::configure_global(threads as usize)?;
+    }
+
+    if cli.list {
+        list_days(&registry);
+        return Ok(());
+    }
+
+    let options = cli.run_options();
+    let selection = select_days(&cli, &registry)?;
+
+    if let Some(runs) = cli.bench {
+        for (solution, source) in &selection {
+            let bench = solution.bench(source, &options, runs as usize)?;
+            bench::print_bench(&bench);
+        }
+        return Ok(());
+    }
+
+    if let Some(budget) = cli.profile {
+        for (solution, source) in &selection {
+            let profile = solution.profile(source, &options, budget)?;
+            profile::print_profile(&profile);
+        }
+        return Ok(());
+    }
+
+    if cli.animate {
+        for (solution, source) in &selection {
+            animate_day(&cli, *solution, source)?;
+        }
+        return Ok(());
+    }
+
+    let answers = if cli.verify {
+        let path = cli
+            .answers
+            .clone()
+            .unwrap_or_else(|| Answers::default_path(cli.year));
+        Some(Answers::load(&path)?)
+    } else {
+        None
+    };
+
+    let mut reporter = report::reporter(cli.format, cli.day.is_none());
+    let results: Vec<_> = selection
+        .iter()
+        .map(|(solution, source)| solution.run(source, &options, reporter.as_mut()))
+        .collect();
+    reporter.finish(&results);
+
+    if let Some(answers) = answers {
+        let verification = answers.verify(&results);
+        eprintln!("{}", verification);
+        if !verification.is_ok() {
+            return Err(anyhow!(
+                "{} answer(s) did not match the answers file",
+                verification.mismatches.len()
+            ));
+        }
+    }
+
+    let failures = results
+        .iter()
+        .map(|result| result.failures.len())
+        .sum::<usize>();
+    if failures > 0 {
+        return Err(anyhow!("{} failure(s) while running", failures));
+    }
+
+    Ok(())
+}
+
+fn find_day(registry: &Registry, year: u16, day: u8) -> Result<&dyn DynSolution> {
+    registry.get(year, day).ok_or_else(|| {
+        anyhow!(
+            "Day {} of {} not implemented, available days: {}",
+            day,
+            year,
+            registry.days(year).iter().join(", ")
+        )
+    })
+}
+
+fn select_days<'a>(
+    cli: &cli::Cli,
+    registry: &'a Registry,
+) -> Result<Vec<(&'a dyn DynSolution, InputSource)>> {
+    if let Some(day) = cli.day {
+        let solution = find_day(registry, cli.year, day)?;
+        let source = cli
+            .input
+            .as_deref()
+            .map(InputSource::from_arg)
+            .unwrap_or_else(|| InputSource::for_day(cli.year, day));
+        return Ok(vec![(solution, source)]);
+    }
+
+    Ok(registry
+        .in_range(cli.year, cli.selected_days())
+        .map(|solution| (solution, InputSource::for_day(cli.year, solution.day())))
+        .collect())
+}
+
+fn animate_day(cli: &cli::Cli, solution: &dyn DynSolution, source: &InputSource) -> Result<()> {
+    let parts: Vec<u8> = [1, 2]
+        .into_iter()
+        .filter(|&part| cli.part.includes(part))
+        .collect();
+    let mut animated = false;
+
+    for &part in &parts {
+        // Both parts recorded at once each get a file of their own.
+        let path = cli.record.as_ref().map(|path| match parts.len() {
+            1 => path.clone(),
+            _ => part_path(path, part),
+        });
+        let steps = solution.animate(source, part, &mut || {
+            animate::sink(path.as_deref(), cli.fps)
+        })?;
+
+        if let Some(steps) = steps {
+            animated = true;
+            match &path {
+                Some(path) => eprintln!(
+                    "Recorded {} steps of part {} to {}",
+                    steps,
+                    part,
+                    path.display()
+                ),
+                None => eprintln!("Part {} finished after {} steps", part, steps),
+            }
+        }
+    }
+
+    if !animated {
+        return Err(anyhow!(
+            "Day {:02} has no animation for part {}",
+            solution.day(),
+            parts.iter().join(" or ")
+        ));
+    }
+    Ok(())
+}
+
+/// `path` with `-partN` added before its extension.
+fn part_path(path: &Path, part: u8) -> PathBuf {
+    let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+    let name = match path.extension() {
+        Some(extension) => format!("{}-part{}.{}", stem, part, extension.to_string_lossy()),
+        None => format!("{}-part{}", stem, part),
+    };
+    path.with_file_name(name)
+}
+
+fn fetch(year: u16, day: u8) -> Result<()> {
+    let cache = FileCache::new(input::input_dir());
+    match provider::fetch_into_cache(&cache, &HttpProvider::from_env(), year, day)? {
+        Fetched::Cached(path) => {
+            println!("Day {:02} input already cached at {}", day, path.display())
+        }
+        Fetched::Downloaded(path) => {
+            println!("Downloaded day {:02} input to {}", day, path.display())
+        }
+    }
+    Ok(())
+}
+
+fn watch(
+    registry: &Registry,
+    year: u16,
+    day: u8,
+    input: Option<PathBuf>,
+    parts: Parts,
+) -> Result<()> {
+    let solution = find_day(registry, year, day)?;
+    let input = input.unwrap_or_else(|| FileCache::new(input::input_dir()).path(year, day));
+    let files = std::iter::once(input)
+        .chain(input::sample_paths(year, day))
+        .collect();
+    let options = RunOptions {
+        parts,
+        timeout: None,
+    };
+    watch::watch(solution, files, &options, WATCH_INTERVAL)
+}
+
+fn new_day(year: u16, day: u8) -> Result<()> {
+    for path in scaffold::scaffold(Path::new(""), year, day)? {
+        println!("Wrote {}", path.display());
+    }
+    Ok(())
+}
+
+fn list_days(registry: &Registry) {
+    for solution in registry.iter() {
+        println!(
+            "{} Day {:02}  {}",
+            solution.year(),
+            solution.day(),
+            solution.answer_type()
+        );
     }
 }
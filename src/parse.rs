@@ -0,0 +1,268 @@
+use std::{
+    fmt::{Display, Formatter},
+    str::FromStr,
+};
+
+/// How much of the offending text an error quotes.
+const MAX_QUOTED_CHARS: usize = 40;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// 1-based line of the offending text.
+    pub line: usize,
+    /// 1-based column, in characters, of the offending text.
+    pub column: usize,
+    pub text: String,
+    pub message: String,
+}
+
+impl ParseError {
+    /// Moves an error about `inner`, a slice of `outer`, to its place in `outer`.
+    fn within(self, outer: &str, inner: &str) -> Self {
+        let (line, column) = position(outer, inner);
+        ParseError {
+            column: if self.line == 1 {
+                column + self.column - 1
+            } else {
+                self.column
+            },
+            line: line + self.line - 1,
+            ..self
+        }
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: {}",
+            self.line, self.column, self.message
+        )?;
+        if self.text.is_empty() {
+            return Ok(());
+        }
+        if self.text.chars().count() > MAX_QUOTED_CHARS {
+            let quoted: String = self.text.chars().take(MAX_QUOTED_CHARS).collect();
+            write!(f, " at `{}…`", quoted)
+        } else {
+            write!(f, " at `{}`", self.text)
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// The 1-based line and column `inner` starts at, if it is a slice of `outer`.
+fn position(outer: &str, inner: &str) -> (usize, usize) {
+    let start = outer.as_ptr() as usize;
+    let offset = (inner.as_ptr() as usize)
+        .checked_sub(start)
+        .filter(|&offset| offset <= outer.len())
+        .unwrap_or(0);
+    let before = &outer[..offset];
+    let line = before.matches('\n').count() + 1;
+    let line_start = before.rfind('\n').map_or(0, |newline| newline + 1);
+    let column = before[line_start..].chars().count() + 1;
+    (line, column)
+}
+
+/// The whole text being parsed, used to locate the slices taken from it.
+///
+/// Positions are worked out from where a slice sits in the text, so parsers can keep
+/// splitting and slicing `&str`s as usual and only need a `Text` to turn a bad slice into
+/// a [`ParseError`] with its line and column.
+#[derive(Debug, Clone, Copy)]
+pub struct Text<'a> {
+    text: &'a str,
+}
+
+impl<'a> Text<'a> {
+    pub fn new(text: &'a str) -> Self {
+        Text { text }
+    }
+
+    pub fn as_str(&self) -> &'a str {
+        self.text
+    }
+
+    /// An error about `part`, which has to be a slice of this text.
+    pub fn error(&self, part: &str, message: impl Into<String>) -> ParseError {
+        let (line, column) = position(self.text, part);
+        ParseError {
+            line,
+            column,
+            text: part.to_string(),
+            message: message.into(),
+        }
+    }
+
+    pub fn lines(&self) -> std::str::Lines<'a> {
+        self.text.lines()
+    }
+
+    /// The blank-line separated sections of the text.
+    pub fn sections(&self) -> impl Iterator<Item = &'a str> {
+        self.text
+            .split("\n\n")
+            .filter(|section| !section.is_empty())
+    }
+
+    /// Splits `part` around the first `separator`, which it must contain.
+    pub fn split_once(
+        &self,
+        part: &'a str,
+        separator: &str,
+    ) -> Result<(&'a str, &'a str), ParseError> {
+        part.split_once(separator)
+            .ok_or_else(|| self.error(part, format!("expected `{}`", separator.escape_debug())))
+    }
+
+    /// Strips `prefix` from `part`, which must start with it.
+    pub fn strip_prefix(&self, part: &'a str, prefix: &str) -> Result<&'a str, ParseError> {
+        part.strip_prefix(prefix)
+            .ok_or_else(|| self.error(part, format!("expected `{}`", prefix.escape_debug())))
+    }
+
+    /// Parses `part`, minus surrounding whitespace, with its `FromStr`, reporting failures at `part`.
+    ///
+    /// If the parser itself failed with a [`ParseError`], that error is moved to its place
+    /// in this text, so nested parsers only ever deal with their own input.
+    pub fn parse<T>(&self, part: &str) -> Result<T, ParseError>
+    where
+        T: FromStr,
+        T::Err: Into<anyhow::Error>,
+    {
+        let part = part.trim();
+        part.parse().map_err(|error: T::Err| {
+            let error: anyhow::Error = error.into();
+            match error.downcast::<ParseError>() {
+                Ok(error) => error.within(self.text, part),
+                Err(error) => self.error(part, error.to_string()),
+            }
+        })
+    }
+
+    /// Parses each line of the text with `T`'s `FromStr`.
+    pub fn parse_lines<T>(&self) -> Result<Vec<T>, ParseError>
+    where
+        T: FromStr,
+        T::Err: Into<anyhow::Error>,
+    {
+        self.lines().map(|line| self.parse(line)).collect()
+    }
+
+    /// The rows of a rectangular grid, failing on an empty grid or a row of the wrong width.
+    pub fn grid(&self) -> Result<Vec<&'a str>, ParseError> {
+        let rows: Vec<_> = self.lines().collect();
+        let width = match rows.first() {
+            Some(row) if !row.is_empty() => row.chars().count(),
+            _ => return Err(self.error(self.text, "expected a grid, found no rows")),
+        };
+        if let Some(row) = rows.iter().find(|row| row.chars().count() != width) {
+            return Err(self.error(
+                row,
+                format!(
+                    "expected a row of {} characters, found {}",
+                    width,
+                    row.chars().count()
+                ),
+            ));
+        }
+        Ok(rows)
+    }
+
+    /// An error about the character at `column` (0-based, in characters) of `row`.
+    pub fn char_error(&self, row: &str, column: usize, message: impl Into<String>) -> ParseError {
+        let start = row
+            .char_indices()
+            .nth(column)
+            .map_or(row.len(), |(index, _)| index);
+        let end = row[start..]
+            .chars()
+            .next()
+            .map_or(start, |c| start + c.len_utf8());
+        self.error(&row[start..end], message)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, PartialEq)]
+    struct Pair(i64, i64);
+
+    impl FromStr for Pair {
+        type Err = anyhow::Error;
+
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            let text = Text::new(s);
+            let (a, b) = text.split_once(s, ",")?;
+            Ok(Pair(text.parse(a)?, text.parse(b)?))
+        }
+    }
+
+    #[test]
+    fn locates_slices() {
+        let text = Text::new("ab\ncdé\nfg");
+        let line = text.lines().nth(1).unwrap();
+        let error = text.error(&line[2..], "bad");
+        assert_eq!((error.line, error.column), (2, 3));
+        assert_eq!(error.text, "é");
+        assert_eq!(error.to_string(), "line 2, column 3: bad at `é`");
+
+        let error = text.char_error(text.lines().nth(2).unwrap(), 1, "no g");
+        assert_eq!((error.line, error.column, error.text.as_str()), (3, 2, "g"));
+    }
+
+    #[test]
+    fn nested_errors_point_into_the_whole_text() {
+        let text = Text::new("1,2\n3,x\n");
+        let error = text.parse_lines::<Pair>().unwrap_err();
+        assert_eq!((error.line, error.column), (2, 3));
+        assert_eq!(error.text, "x");
+        assert!(error.message.contains("invalid digit"));
+
+        let text = Text::new("1,2\n1;2");
+        let error = text
+            .parse::<Pair>(text.lines().nth(1).unwrap())
+            .unwrap_err();
+        assert_eq!((error.line, error.column), (2, 1));
+        assert_eq!(error.message, "expected `,`");
+
+        assert_eq!(
+            Text::new("1,2\n3,4").parse_lines::<Pair>().unwrap(),
+            vec![Pair(1, 2), Pair(3, 4)]
+        );
+    }
+
+    #[test]
+    fn grids_have_to_be_rectangular() {
+        assert_eq!(Text::new("ab\ncd\n").grid().unwrap(), vec!["ab", "cd"]);
+
+        let error = Text::new("abc\nab\nabc").grid().unwrap_err();
+        assert_eq!((error.line, error.column), (2, 1));
+        assert_eq!(error.message, "expected a row of 3 characters, found 2");
+
+        assert!(Text::new("").grid().is_err());
+    }
+
+    #[test]
+    fn sections_skip_trailing_blank_lines() {
+        let text = Text::new("a\nb\n\nc\n\n");
+        let sections: Vec<_> = text.sections().collect();
+        assert_eq!(sections, vec!["a\nb", "c"]);
+        let error = text.error(&sections[1][..1], "bad");
+        assert_eq!(error.line, 4);
+    }
+
+    #[test]
+    fn long_text_is_truncated() {
+        let long = "x".repeat(50);
+        let error = Text::new(&long).error(&long, "too long");
+        assert!(error
+            .to_string()
+            .ends_with(&format!("`{}…`", "x".repeat(40))));
+    }
+}
@@ -0,0 +1,233 @@
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Mutex,
+    },
+    time::{Duration, Instant},
+};
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static SPANS: Mutex<Option<HashMap<&'static str, SpanStats>>> = Mutex::new(None);
+
+/// Times the code between its creation and the end of its scope, see [`span`].
+#[must_use = "a span measures until it is dropped"]
+pub struct Span {
+    name: &'static str,
+    start: Option<Instant>,
+}
+
+/// Marks a phase of a solution, e.g. `let _span = profile::span("cost map");`.
+///
+/// Spans are only recorded while profiling is enabled, otherwise they cost an atomic load.
+/// They can be opened from any thread, so phases that run on rayon's pool add up across workers.
+pub fn span(name: &'static str) -> Span {
+    Span {
+        name,
+        start: ENABLED.load(Ordering::Relaxed).then(Instant::now),
+    }
+}
+
+impl Drop for Span {
+    fn drop(&mut self) {
+        let Some(start) = self.start else {
+            return;
+        };
+        let elapsed = start.elapsed();
+        let mut spans = SPANS
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        spans
+            .get_or_insert_with(HashMap::new)
+            .entry(self.name)
+            .or_insert_with(|| SpanStats::new(self.name))
+            .record(elapsed);
+    }
+}
+
+/// Starts or stops recording spans.
+pub fn set_enabled(enabled: bool) {
+    ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+/// Returns the spans recorded so far, longest total first, and starts over.
+pub fn take_spans() -> Vec<SpanStats> {
+    let mut spans = SPANS
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    let mut spans: Vec<_> = spans.take().unwrap_or_default().into_values().collect();
+    spans.sort_by(|a, b| b.total.cmp(&a.total).then(a.name.cmp(b.name)));
+    spans
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpanStats {
+    pub name: &'static str,
+    pub calls: usize,
+    pub total: Duration,
+    pub min: Duration,
+    pub max: Duration,
+}
+
+impl SpanStats {
+    fn new(name: &'static str) -> Self {
+        SpanStats {
+            name,
+            calls: 0,
+            total: Duration::ZERO,
+            min: Duration::MAX,
+            max: Duration::ZERO,
+        }
+    }
+
+    fn record(&mut self, elapsed: Duration) {
+        self.calls += 1;
+        self.total += elapsed;
+        self.min = self.min.min(elapsed);
+        self.max = self.max.max(elapsed);
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct PartProfile {
+    pub part: u8,
+    pub iterations: usize,
+    pub elapsed: Duration,
+    pub spans: Vec<SpanStats>,
+}
+
+#[derive(Debug, Clone)]
+pub struct DayProfile {
+    pub day: u8,
+    pub parts: Vec<PartProfile>,
+}
+
+/// Calls `f` until `budget` has passed, at least once, returning how often it ran and for how long.
+pub fn repeat_for<F>(budget: Duration, mut f: F) -> anyhow::Result<(usize, Duration)>
+where
+    F: FnMut() -> anyhow::Result<()>,
+{
+    let start = Instant::now();
+    let mut iterations = 0;
+    loop {
+        f()?;
+        iterations += 1;
+        let elapsed = start.elapsed();
+        if elapsed >= budget {
+            return Ok((iterations, elapsed));
+        }
+    }
+}
+
+pub fn print_profile(profile: &DayProfile) {
+    print!("{}", render_profile(profile));
+}
+
+fn render_profile(profile: &DayProfile) -> String {
+    let mut out = format!("Day {:02}\n====================\n", profile.day);
+    for part in &profile.parts {
+        out.push_str(&format!(
+            "Part {}: {} iteration(s) in {:.2?}, {:.2?} per iteration\n",
+            part.part,
+            part.iterations,
+            part.elapsed,
+            part.elapsed.div_f64(part.iterations.max(1) as f64)
+        ));
+        if part.spans.is_empty() {
+            continue;
+        }
+
+        let name_width = part
+            .spans
+            .iter()
+            .map(|span| span.name.len())
+            .max()
+            .unwrap_or(0)
+            .max("Span".len());
+        out.push_str(&format!(
+            "  {:<name_width$} | {:>7} | {:>10} | {:>10} | {:>10} | {:>10}\n",
+            "Span", "Calls", "Total", "Mean", "Min", "Max"
+        ));
+        for span in &part.spans {
+            out.push_str(&format!(
+                "  {:<name_width$} | {:>7} | {:>10} | {:>10} | {:>10} | {:>10}\n",
+                span.name,
+                span.calls,
+                format!("{:.2?}", span.total),
+                format!("{:.2?}", span.total.div_f64(span.calls as f64)),
+                format!("{:.2?}", span.min),
+                format!("{:.2?}", span.max),
+            ));
+        }
+    }
+    out.push('\n');
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn records_spans_only_while_enabled() {
+        drop(span("profile-test-disabled"));
+
+        set_enabled(true);
+        for _ in 0..3 {
+            let _span = span("profile-test-enabled");
+        }
+        set_enabled(false);
+        drop(span("profile-test-enabled"));
+
+        let spans = take_spans();
+        let recorded: Vec<_> = spans
+            .iter()
+            .filter(|span| span.name.starts_with("profile-test-"))
+            .collect();
+        assert_eq!(recorded.len(), 1);
+        assert_eq!(recorded[0].name, "profile-test-enabled");
+        assert_eq!(recorded[0].calls, 3);
+        assert!(recorded[0].min <= recorded[0].max);
+    }
+
+    #[test]
+    fn repeats_until_the_budget_is_spent() {
+        let mut calls = 0;
+        let (iterations, elapsed) = repeat_for(Duration::from_millis(5), || {
+            calls += 1;
+            Ok(())
+        })
+        .unwrap();
+        assert_eq!(iterations, calls);
+        assert!(elapsed >= Duration::from_millis(5));
+
+        let (iterations, _) = repeat_for(Duration::ZERO, || Ok(())).unwrap();
+        assert_eq!(iterations, 1);
+    }
+
+    #[test]
+    fn renders_span_table() {
+        let profile = DayProfile {
+            day: 20,
+            parts: vec![PartProfile {
+                part: 1,
+                iterations: 4,
+                elapsed: Duration::from_millis(8),
+                spans: vec![SpanStats {
+                    name: "cheat search",
+                    calls: 4,
+                    total: Duration::from_millis(6),
+                    min: Duration::from_millis(1),
+                    max: Duration::from_millis(2),
+                }],
+            }],
+        };
+        assert_eq!(
+            render_profile(&profile),
+            "Day 20\n====================\n\
+             Part 1: 4 iteration(s) in 8.00ms, 2.00ms per iteration\n  \
+             Span         |   Calls |      Total |       Mean |        Min |        Max\n  \
+             cheat search |       4 |     6.00ms |     1.50ms |     1.00ms |     2.00ms\n\n"
+        );
+    }
+}
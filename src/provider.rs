@@ -0,0 +1,237 @@
+use std::{env, fs, path::PathBuf};
+
+use anyhow::{anyhow, Context, Result};
+
+use crate::input;
+
+pub const BASE_URL_VAR: &str = "AOC_BASE_URL";
+pub const SESSION_VAR: &str = "AOC_SESSION";
+const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+const USER_AGENT: &str = "github.com/MadaraUchiha/aoc-2024";
+
+/// Somewhere puzzle inputs can be obtained from.
+pub trait InputProvider {
+    fn fetch(&self, year: u16, day: u8) -> Result<String>;
+}
+
+/// Inputs stored on disk as `YYYY/dayNN.txt`, the layout `InputSource::for_day` reads.
+pub struct FileCache {
+    dir: PathBuf,
+}
+
+impl FileCache {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        FileCache { dir: dir.into() }
+    }
+
+    pub fn path(&self, year: u16, day: u8) -> PathBuf {
+        input::day_path(&self.dir, year, day)
+    }
+
+    pub fn contains(&self, year: u16, day: u8) -> bool {
+        self.path(year, day).is_file()
+    }
+
+    pub fn store(&self, year: u16, day: u8, input: &str) -> Result<PathBuf> {
+        let path = self.path(year, day);
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)
+                .with_context(|| format!("Failed to create {}", dir.display()))?;
+        }
+        fs::write(&path, input)
+            .with_context(|| format!("Failed to write input to {}", path.display()))?;
+        Ok(path)
+    }
+}
+
+impl InputProvider for FileCache {
+    fn fetch(&self, year: u16, day: u8) -> Result<String> {
+        let path = self.path(year, day);
+        fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read input from {}", path.display()))
+    }
+}
+
+/// Downloads inputs from the Advent of Code website, or anything serving the same paths.
+pub struct HttpProvider {
+    base_url: String,
+    session: Option<String>,
+}
+
+impl HttpProvider {
+    pub fn new(base_url: impl Into<String>, session: Option<String>) -> Self {
+        HttpProvider {
+            base_url: base_url.into(),
+            session,
+        }
+    }
+
+    /// Uses `$AOC_BASE_URL` (defaults to adventofcode.com) and the `$AOC_SESSION` cookie.
+    pub fn from_env() -> Self {
+        let base_url = env::var(BASE_URL_VAR).unwrap_or_else(|_| DEFAULT_BASE_URL.to_string());
+        HttpProvider::new(base_url, env::var(SESSION_VAR).ok())
+    }
+
+    pub fn url(&self, year: u16, day: u8) -> String {
+        format!(
+            "{}/{}/day/{}/input",
+            self.base_url.trim_end_matches('/'),
+            year,
+            day
+        )
+    }
+}
+
+impl InputProvider for HttpProvider {
+    fn fetch(&self, year: u16, day: u8) -> Result<String> {
+        let session = self.session.as_deref().ok_or(anyhow!(
+            "Set {} to your adventofcode.com session cookie to download inputs",
+            SESSION_VAR
+        ))?;
+        let url = self.url(year, day);
+
+        let response = ureq::get(&url)
+            .set("Cookie", &format!("session={}", session))
+            .set("User-Agent", USER_AGENT)
+            .call()
+            .map_err(|error| match error {
+                ureq::Error::Status(status, _) => {
+                    anyhow!(
+                        "Failed to download {}: server responded with {}",
+                        url,
+                        status
+                    )
+                }
+                error => anyhow::Error::new(error).context(format!("Failed to download {}", url)),
+            })?;
+
+        response
+            .into_string()
+            .with_context(|| format!("Failed to read the response from {}", url))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Fetched {
+    Cached(PathBuf),
+    Downloaded(PathBuf),
+}
+
+/// Makes sure `cache` has the input for a day, only asking `remote` for it when it's missing.
+pub fn fetch_into_cache(
+    cache: &FileCache,
+    remote: &dyn InputProvider,
+    year: u16,
+    day: u8,
+) -> Result<Fetched> {
+    if cache.contains(year, day) {
+        return Ok(Fetched::Cached(cache.path(year, day)));
+    }
+
+    let input = remote.fetch(year, day)?;
+    cache.store(year, day, &input).map(Fetched::Downloaded)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        io::{BufRead, BufReader, Write},
+        net::TcpListener,
+        thread::{self, JoinHandle},
+    };
+
+    use super::*;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!("aoc2024-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    /// Answers a single request with `status` and `body`, returning the request head it got.
+    fn stand_in_server(status: &'static str, body: &'static str) -> (String, JoinHandle<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+
+        let handle = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut request = String::new();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if line.trim().is_empty() {
+                    break;
+                }
+                request.push_str(&line);
+            }
+            write!(
+                stream,
+                "HTTP/1.1 {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                status,
+                body.len(),
+                body
+            )
+            .unwrap();
+            request
+        });
+
+        (base_url, handle)
+    }
+
+    struct Unreachable;
+
+    impl InputProvider for Unreachable {
+        fn fetch(&self, _year: u16, day: u8) -> Result<String> {
+            panic!("Day {} should have been served from the cache", day);
+        }
+    }
+
+    #[test]
+    fn downloads_into_cache() {
+        let (base_url, server) = stand_in_server("200 OK", "1   2\n3   4\n");
+        let cache = FileCache::new(temp_dir("download"));
+        let remote = HttpProvider::new(base_url, Some("secret".to_string()));
+
+        let fetched = fetch_into_cache(&cache, &remote, 2024, 1).unwrap();
+        assert_eq!(fetched, Fetched::Downloaded(cache.path(2024, 1)));
+        assert!(cache.path(2024, 1).ends_with("2024/day01.txt"));
+        assert_eq!(cache.fetch(2024, 1).unwrap(), "1   2\n3   4\n");
+
+        let request = server.join().unwrap();
+        assert!(request.starts_with("GET /2024/day/1/input HTTP/1.1"));
+        assert!(request.contains("session=secret"));
+    }
+
+    #[test]
+    fn never_downloads_cached_inputs() {
+        let cache = FileCache::new(temp_dir("cached"));
+        cache.store(2024, 5, "cached").unwrap();
+
+        let fetched = fetch_into_cache(&cache, &Unreachable, 2024, 5).unwrap();
+        assert_eq!(fetched, Fetched::Cached(cache.path(2024, 5)));
+        assert_eq!(cache.fetch(2024, 5).unwrap(), "cached");
+    }
+
+    #[test]
+    fn reports_server_errors() {
+        let (base_url, server) = stand_in_server("404 Not Found", "not yet");
+        let cache = FileCache::new(temp_dir("missing"));
+        let remote = HttpProvider::new(base_url, Some("secret".to_string()));
+
+        let error = fetch_into_cache(&cache, &remote, 2023, 25).unwrap_err();
+        assert!(error.to_string().contains("404"));
+        assert!(!cache.contains(2023, 25));
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn requires_a_session() {
+        let remote = HttpProvider::new(DEFAULT_BASE_URL, None);
+        assert!(remote
+            .fetch(2024, 1)
+            .unwrap_err()
+            .to_string()
+            .contains(SESSION_VAR));
+    }
+}
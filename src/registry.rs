@@ -0,0 +1,214 @@
+use std::{any::type_name, collections::BTreeMap, ops::RangeInclusive, time::Duration};
+
+use anyhow::Result;
+
+use crate::{
+    animate::FrameSink,
+    bench::DayBench,
+    input::InputSource,
+    profile::DayProfile,
+    report::Reporter,
+    solution::{DayResult, RunOptions, Solution},
+};
+
+/// Object-safe view of a [`Solution`], so days can be stored and run dynamically.
+pub trait DynSolution {
+    fn year(&self) -> u16;
+    fn day(&self) -> u8;
+    fn answer_type(&self) -> &'static str;
+    fn run(
+        &self,
+        source: &InputSource,
+        options: &RunOptions,
+        reporter: &mut dyn Reporter,
+    ) -> DayResult;
+    fn bench(&self, source: &InputSource, options: &RunOptions, runs: usize) -> Result<DayBench>;
+    fn profile(
+        &self,
+        source: &InputSource,
+        options: &RunOptions,
+        budget: Duration,
+    ) -> Result<DayProfile>;
+    /// Records one part's animation, `None` if the part has none.
+    fn animate(
+        &self,
+        source: &InputSource,
+        part: u8,
+        open: &mut dyn FnMut() -> Result<Box<dyn FrameSink>>,
+    ) -> Result<Option<usize>>;
+    /// Solves one part of an in-memory input and formats the answer.
+    fn answer(&self, part: u8, input: &str) -> Result<String>;
+}
+
+impl<S: Solution + 'static> DynSolution for S {
+    fn year(&self) -> u16 {
+        Solution::year(self)
+    }
+
+    fn day(&self) -> u8 {
+        Solution::day(self)
+    }
+
+    fn answer_type(&self) -> &'static str {
+        let full_name = type_name::<S::Answer>();
+        full_name.rsplit("::").next().unwrap_or(full_name)
+    }
+
+    fn run(
+        &self,
+        source: &InputSource,
+        options: &RunOptions,
+        reporter: &mut dyn Reporter,
+    ) -> DayResult {
+        Solution::run(self, source, options, reporter)
+    }
+
+    fn bench(&self, source: &InputSource, options: &RunOptions, runs: usize) -> Result<DayBench> {
+        Solution::bench(self, source, options, runs)
+    }
+
+    fn profile(
+        &self,
+        source: &InputSource,
+        options: &RunOptions,
+        budget: Duration,
+    ) -> Result<DayProfile> {
+        Solution::profile(self, source, options, budget)
+    }
+
+    fn animate(
+        &self,
+        source: &InputSource,
+        part: u8,
+        open: &mut dyn FnMut() -> Result<Box<dyn FrameSink>>,
+    ) -> Result<Option<usize>> {
+        Solution::animate(self, source, part, open)
+    }
+
+    fn answer(&self, part: u8, input: &str) -> Result<String> {
+        S::solve(part, input).map(|answer| answer.to_string())
+    }
+}
+
+#[derive(Default)]
+pub struct Registry {
+    solutions: BTreeMap<(u16, u8), Box<dyn DynSolution>>,
+}
+
+impl Registry {
+    pub fn register<S: Solution + 'static>(&mut self, solution: S) {
+        let key = (Solution::year(&solution), Solution::day(&solution));
+        if self.solutions.insert(key, Box::new(solution)).is_some() {
+            panic!("{} day {:02} registered twice", key.0, key.1);
+        }
+    }
+
+    pub fn get(&self, year: u16, day: u8) -> Option<&dyn DynSolution> {
+        self.solutions
+            .get(&(year, day))
+            .map(|solution| solution.as_ref())
+    }
+
+    /// Every registered day, ordered by year and then day.
+    pub fn iter(&self) -> impl Iterator<Item = &dyn DynSolution> {
+        self.solutions.values().map(|solution| solution.as_ref())
+    }
+
+    pub fn in_range(
+        &self,
+        year: u16,
+        days: RangeInclusive<u8>,
+    ) -> impl Iterator<Item = &dyn DynSolution> {
+        self.solutions
+            .range((year, *days.start())..=(year, *days.end()))
+            .map(|(_, solution)| solution.as_ref())
+    }
+
+    pub fn days(&self, year: u16) -> Vec<u8> {
+        self.solutions
+            .keys()
+            .filter(|(y, _)| *y == year)
+            .map(|&(_, day)| day)
+            .collect()
+    }
+
+    pub fn years(&self) -> Vec<u16> {
+        let mut years: Vec<_> = self.solutions.keys().map(|&(year, _)| year).collect();
+        years.dedup();
+        years
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution::DEFAULT_YEAR;
+
+    struct Day01Of2015;
+
+    impl Solution for Day01Of2015 {
+        type Answer = i32;
+        type Parsed<'a> = &'a str;
+        fn day(&self) -> u8 {
+            1
+        }
+
+        fn year(&self) -> u16 {
+            2015
+        }
+
+        fn parse(input: &str) -> Result<Self::Parsed<'_>> {
+            Ok(input)
+        }
+
+        fn part1(input: &&str) -> Result<Self::Answer> {
+            Ok(input.chars().map(|c| if c == '(' { 1 } else { -1 }).sum())
+        }
+
+        fn part2(_input: &&str) -> Result<Self::Answer> {
+            Ok(0)
+        }
+    }
+
+    #[test]
+    fn every_day_is_registered() {
+        let registry = crate::registry();
+        assert_eq!(registry.days(DEFAULT_YEAR), (1..=25).collect::<Vec<_>>());
+        assert!(registry.get(DEFAULT_YEAR, 26).is_none());
+    }
+
+    #[test]
+    fn reports_answer_types() {
+        let registry = crate::registry();
+        assert_eq!(registry.get(2024, 1).unwrap().answer_type(), "usize");
+        assert_eq!(registry.get(2024, 13).unwrap().answer_type(), "f64");
+        assert_eq!(registry.get(2024, 23).unwrap().answer_type(), "String");
+    }
+
+    #[test]
+    fn selects_days_in_range() {
+        let registry = crate::registry();
+        let days: Vec<_> = registry
+            .in_range(DEFAULT_YEAR, 20..=30)
+            .map(|s| s.day())
+            .collect();
+        assert_eq!(days, vec![20, 21, 22, 23, 24, 25]);
+    }
+
+    #[test]
+    fn keys_days_by_year() {
+        // Only the years registered here, so scaffolding a new year doesn't change the answer.
+        let mut registry = Registry::default();
+        registry.register(crate::days::Day01);
+        registry.register(Day01Of2015);
+
+        assert_eq!(registry.years(), vec![2015, 2024]);
+        assert_eq!(registry.days(2015), vec![1]);
+        assert_eq!(
+            registry.get(2015, 1).unwrap().answer(1, "(()(").unwrap(),
+            "2"
+        );
+        assert_eq!(registry.get(2024, 1).unwrap().answer_type(), "usize");
+        assert_eq!(registry.in_range(2015, 1..=25).count(), 1);
+    }
+}
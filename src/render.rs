@@ -0,0 +1,283 @@
+//! Pictures of grids: plain or ANSI-colored text for the terminal, and binary PPM/PGM images.
+
+use std::{collections::HashSet, fmt::Write as _, fs, io::Write as _, path::Path};
+
+use anyhow::{anyhow, Context, Result};
+
+use crate::{vector::Vec2, vector_map::VectorMap};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Color {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+}
+
+impl Color {
+    pub const BLACK: Self = Color::rgb(0, 0, 0);
+    pub const WHITE: Self = Color::rgb(255, 255, 255);
+    pub const GRAY: Self = Color::rgb(128, 128, 128);
+    pub const RED: Self = Color::rgb(220, 50, 47);
+    pub const GREEN: Self = Color::rgb(80, 200, 80);
+    pub const BLUE: Self = Color::rgb(38, 139, 210);
+    pub const YELLOW: Self = Color::rgb(240, 200, 40);
+
+    pub const fn rgb(r: u8, g: u8, b: u8) -> Self {
+        Color { r, g, b }
+    }
+
+    /// A color that stays distinct from its neighbors in the sequence, for telling regions apart.
+    pub fn palette(index: usize) -> Self {
+        // Stepping the hue by the golden angle keeps consecutive colors far apart.
+        let hue = (index as f64 * 137.507_764) % 360.0;
+        let sector = hue / 60.0;
+        let rising = (sector % 1.0 * 200.0) as u8 + 55;
+        let falling = 255 - rising + 55;
+        match sector as u8 {
+            0 => Color::rgb(255, rising, 55),
+            1 => Color::rgb(falling, 255, 55),
+            2 => Color::rgb(55, 255, rising),
+            3 => Color::rgb(55, falling, 255),
+            4 => Color::rgb(rising, 55, 255),
+            _ => Color::rgb(255, 55, falling),
+        }
+    }
+
+    /// Perceived brightness, for grayscale images.
+    pub fn luma(&self) -> u8 {
+        ((self.r as u32 * 299 + self.g as u32 * 587 + self.b as u32 * 114) / 1000) as u8
+    }
+}
+
+/// How one position of the grid is drawn.
+///
+/// The glyph is what text output shows, the color tints it in the terminal and fills the
+/// position's pixels in images. Uncolored cells are left as they are in the terminal and
+/// drawn black in images.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cell {
+    pub glyph: char,
+    pub color: Option<Color>,
+}
+
+impl Cell {
+    pub fn new(glyph: char) -> Self {
+        Cell { glyph, color: None }
+    }
+
+    pub fn colored(glyph: char, color: Color) -> Self {
+        Cell {
+            glyph,
+            color: Some(color),
+        }
+    }
+}
+
+impl Default for Cell {
+    fn default() -> Self {
+        Cell::new(' ')
+    }
+}
+
+/// A change drawn over some positions, such as a path or a robot.
+#[derive(Debug, Clone)]
+pub struct Overlay {
+    positions: HashSet<Vec2>,
+    glyph: Option<char>,
+    color: Option<Color>,
+}
+
+impl Overlay {
+    /// Recolors `positions`, keeping their glyphs.
+    pub fn color(positions: impl IntoIterator<Item = Vec2>, color: Color) -> Self {
+        Overlay {
+            positions: positions.into_iter().collect(),
+            glyph: None,
+            color: Some(color),
+        }
+    }
+
+    /// Draws `positions` with `glyph` instead.
+    pub fn glyph(mut self, glyph: char) -> Self {
+        self.glyph = Some(glyph);
+        self
+    }
+
+    fn apply(&self, cell: &mut Cell) {
+        if let Some(glyph) = self.glyph {
+            cell.glyph = glyph;
+        }
+        if let Some(color) = self.color {
+            cell.color = Some(color);
+        }
+    }
+}
+
+/// Draws a [`VectorMap`] cell by cell, with overlays applied on top in the order they were added.
+pub struct Renderer<'a, T> {
+    map: &'a VectorMap<T>,
+    cell: Box<dyn Fn(&T) -> Cell + 'a>,
+    overlays: Vec<Overlay>,
+}
+
+impl<'a, T> Renderer<'a, T> {
+    pub fn new(map: &'a VectorMap<T>, cell: impl Fn(&T) -> Cell + 'a) -> Self {
+        Renderer {
+            map,
+            cell: Box::new(cell),
+            overlays: Vec::new(),
+        }
+    }
+
+    pub fn overlay(mut self, overlay: Overlay) -> Self {
+        self.overlays.push(overlay);
+        self
+    }
+
+    /// Every cell as it will be drawn, overlays included.
+    pub fn cells(&self) -> VectorMap<Cell> {
+        let mut cells = self.map.map(|value| (self.cell)(value));
+        for overlay in &self.overlays {
+            for position in &overlay.positions {
+                if let Some(cell) = cells.get_mut(position) {
+                    overlay.apply(cell);
+                }
+            }
+        }
+        cells
+    }
+
+    /// The glyphs alone, one line per row.
+    pub fn text(&self) -> String {
+        let mut text = String::new();
+        for row in self.cells().rows() {
+            text.extend(row.iter().map(|cell| cell.glyph));
+            text.push('\n');
+        }
+        text
+    }
+
+    /// The glyphs in 24-bit ANSI colors, for terminals that support them.
+    pub fn ansi(&self) -> String {
+        let mut text = String::new();
+        for row in self.cells().rows() {
+            let mut current = None;
+            for cell in row {
+                if cell.color != current {
+                    match cell.color {
+                        Some(Color { r, g, b }) => write!(text, "\x1b[38;2;{};{};{}m", r, g, b),
+                        None => write!(text, "\x1b[0m"),
+                    }
+                    .unwrap();
+                    current = cell.color;
+                }
+                text.push(cell.glyph);
+            }
+            if current.is_some() {
+                text.push_str("\x1b[0m");
+            }
+            text.push('\n');
+        }
+        text
+    }
+
+    /// A binary (P6) PPM image, each position drawn as a `scale` × `scale` square.
+    pub fn ppm(&self, scale: usize) -> Vec<u8> {
+        self.image("P6", scale, |color| vec![color.r, color.g, color.b])
+    }
+
+    /// A binary (P5) grayscale PGM image, each position drawn as a `scale` × `scale` square.
+    pub fn pgm(&self, scale: usize) -> Vec<u8> {
+        self.image("P5", scale, |color| vec![color.luma()])
+    }
+
+    fn image(&self, magic: &str, scale: usize, pixel: impl Fn(Color) -> Vec<u8>) -> Vec<u8> {
+        let size = self.map.size();
+        let (width, height) = (size.x as usize * scale, size.y as usize * scale);
+        let mut image = Vec::new();
+        write!(image, "{}\n{} {}\n255\n", magic, width, height).unwrap();
+
+        for row in self.cells().rows() {
+            let line: Vec<u8> = row
+                .iter()
+                .flat_map(|cell| pixel(cell.color.unwrap_or(Color::BLACK)).repeat(scale))
+                .collect();
+            for _ in 0..scale {
+                image.extend_from_slice(&line);
+            }
+        }
+        image
+    }
+
+    /// Writes the picture to `path`, as a PPM or PGM image depending on its extension.
+    pub fn write_image(&self, path: &Path, scale: usize) -> Result<()> {
+        let image = match path.extension().and_then(|extension| extension.to_str()) {
+            Some("ppm") => self.ppm(scale),
+            Some("pgm") => self.pgm(scale),
+            _ => {
+                return Err(anyhow!(
+                    "Unsupported image format for {}, expected a .ppm or .pgm file",
+                    path.display()
+                ))
+            }
+        };
+        fs::write(path, image).with_context(|| format!("Failed to write {}", path.display()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn renderer(map: &VectorMap<char>) -> Renderer<'_, char> {
+        Renderer::new(map, |&c| match c {
+            '#' => Cell::colored('#', Color::WHITE),
+            c => Cell::new(c),
+        })
+    }
+
+    #[test]
+    fn overlays_replace_glyphs_and_colors() {
+        let map: VectorMap<char> = "#.\n.#".parse().unwrap();
+        let renderer = renderer(&map)
+            .overlay(Overlay::color([Vec2::new(1, 0), Vec2::new(5, 5)], Color::RED).glyph('@'))
+            .overlay(Overlay::color([Vec2::new(0, 1)], Color::BLUE));
+
+        assert_eq!(renderer.text(), "#@\n.#\n");
+        let cells = renderer.cells();
+        assert_eq!(cells[Vec2::new(1, 0)], Cell::colored('@', Color::RED));
+        assert_eq!(cells[Vec2::new(0, 1)], Cell::colored('.', Color::BLUE));
+    }
+
+    #[test]
+    fn ansi_only_switches_colors_when_they_change() {
+        let map: VectorMap<char> = "##.".parse().unwrap();
+        assert_eq!(renderer(&map).ansi(), "\x1b[38;2;255;255;255m##\x1b[0m.\n");
+    }
+
+    #[test]
+    fn images_have_a_header_and_scaled_pixels() {
+        let map: VectorMap<char> = "#.".parse().unwrap();
+        let renderer = renderer(&map);
+
+        let ppm = renderer.ppm(2);
+        let header = b"P6\n4 2\n255\n";
+        assert_eq!(&ppm[..header.len()], header);
+        let pixels = &ppm[header.len()..];
+        assert_eq!(pixels.len(), 4 * 2 * 3);
+        assert_eq!(
+            &pixels[..12],
+            &[255, 255, 255, 255, 255, 255, 0, 0, 0, 0, 0, 0]
+        );
+
+        let pgm = renderer.pgm(1);
+        assert_eq!(pgm, b"P5\n2 1\n255\n\xff\x00");
+        assert!(renderer.write_image(Path::new("map.png"), 1).is_err());
+    }
+
+    #[test]
+    fn palette_colors_differ() {
+        let colors: HashSet<_> = (0..16).map(Color::palette).collect();
+        assert_eq!(colors.len(), 16);
+    }
+}
@@ -0,0 +1,371 @@
+use std::{str::FromStr, time::Duration};
+
+use anyhow::{anyhow, Result};
+use serde::Serialize;
+
+use crate::{
+    input::InputSource,
+    memory,
+    solution::{DayResult, Failure, PartResult},
+    summary,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Format {
+    #[default]
+    Text,
+    Json,
+    Csv,
+}
+
+impl FromStr for Format {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            "csv" => Ok(Format::Csv),
+            _ => Err(anyhow!("Invalid format: {}, expected text, json or csv", s)),
+        }
+    }
+}
+
+/// Receives progress from the runner and decides how (and when) to print it.
+pub trait Reporter {
+    fn input_read(
+        &mut self,
+        _year: u16,
+        _day: u8,
+        _source: &InputSource,
+        _bytes: usize,
+        _took: Duration,
+    ) {
+    }
+    fn parsed(&mut self, _day: u8, _took: Duration) {}
+    fn part_solved(&mut self, _day: u8, _part: u8, _result: &PartResult) {}
+    fn failed(&mut self, _failure: &Failure) {}
+    fn day_finished(&mut self, _result: &DayResult) {}
+    fn finish(&mut self, _results: &[DayResult]) {}
+}
+
+pub fn reporter(format: Format, show_summary: bool) -> Box<dyn Reporter> {
+    match format {
+        Format::Text => Box::new(TextReporter {
+            show_summary,
+            header_shown: false,
+        }),
+        Format::Json => Box::new(JsonReporter),
+        Format::Csv => Box::new(CsvReporter::default()),
+    }
+}
+
+pub struct TextReporter {
+    show_summary: bool,
+    /// Whether the current day's header is out, which it isn't if its input couldn't be read.
+    header_shown: bool,
+}
+
+impl TextReporter {
+    fn header(&mut self, year: u16, day: u8) {
+        if !self.header_shown {
+            println!("{} Day {:02}", year, day);
+            println!("====================");
+            self.header_shown = true;
+        }
+    }
+}
+
+impl Reporter for TextReporter {
+    fn input_read(
+        &mut self,
+        year: u16,
+        day: u8,
+        source: &InputSource,
+        bytes: usize,
+        took: Duration,
+    ) {
+        self.header(year, day);
+        println!(
+            "Reading input from {} took: {:?}, read {} bytes",
+            source, took, bytes
+        );
+    }
+
+    fn parsed(&mut self, _day: u8, took: Duration) {
+        println!("Parsing took: {:?}", took);
+    }
+
+    fn part_solved(&mut self, _day: u8, part: u8, result: &PartResult) {
+        print!(
+            "Part {} solution: {}, took: {:?}",
+            part, result.answer, result.duration
+        );
+        match result.memory {
+            Some(stats) => println!(
+                ", peak heap: {} in {} allocation(s)",
+                memory::format_bytes(stats.peak_bytes),
+                stats.allocations
+            ),
+            None => println!(),
+        }
+    }
+
+    fn failed(&mut self, failure: &Failure) {
+        match failure.part {
+            Some(part) => println!("Part {} failed: {}", part, failure.message),
+            None => {
+                self.header(failure.year, failure.day);
+                println!("Failed: {}", failure.message);
+            }
+        }
+    }
+
+    fn day_finished(&mut self, _result: &DayResult) {
+        self.header_shown = false;
+        println!();
+    }
+
+    fn finish(&mut self, results: &[DayResult]) {
+        if self.show_summary {
+            summary::print_summary(results);
+        }
+    }
+}
+
+/// One row of JSON or CSV output. Missing values are `null` in JSON and empty in CSV.
+#[derive(Debug, Serialize, PartialEq)]
+struct Record<'a> {
+    year: u16,
+    day: u8,
+    part: Option<u8>,
+    answer: Option<&'a str>,
+    duration_ns: Option<u64>,
+    /// The day's parse time, repeated on each of its records.
+    parse_ns: Option<u64>,
+    input_bytes: usize,
+    /// Only measured with the `alloc-stats` feature.
+    peak_bytes: Option<usize>,
+    allocations: Option<usize>,
+    error: Option<&'a str>,
+}
+
+impl<'a> Record<'a> {
+    fn solved(result: &DayResult, part: u8, part_result: &'a PartResult) -> Self {
+        Record {
+            year: result.year,
+            day: result.day,
+            part: Some(part),
+            answer: Some(&part_result.answer),
+            duration_ns: Some(part_result.duration.as_nanos() as u64),
+            parse_ns: parse_ns(result),
+            input_bytes: result.input_bytes,
+            peak_bytes: part_result.memory.map(|stats| stats.peak_bytes),
+            allocations: part_result.memory.map(|stats| stats.allocations),
+            error: None,
+        }
+    }
+
+    fn failed(result: &DayResult, failure: &'a Failure) -> Self {
+        Record {
+            year: result.year,
+            day: result.day,
+            part: failure.part,
+            answer: None,
+            duration_ns: None,
+            parse_ns: parse_ns(result),
+            input_bytes: result.input_bytes,
+            peak_bytes: None,
+            allocations: None,
+            error: Some(&failure.message),
+        }
+    }
+}
+
+fn parse_ns(result: &DayResult) -> Option<u64> {
+    result
+        .parse_duration
+        .map(|duration| duration.as_nanos() as u64)
+}
+
+fn records(results: &[DayResult]) -> impl Iterator<Item = Record<'_>> {
+    results.iter().flat_map(|result| {
+        let day_failures = result
+            .failures
+            .iter()
+            .filter(|failure| failure.part.is_none())
+            .map(|failure| Record::failed(result, failure));
+        let parts = [1, 2].into_iter().filter_map(|part| {
+            if let Some(part_result) = result.part(part) {
+                return Some(Record::solved(result, part, part_result));
+            }
+            result
+                .failures
+                .iter()
+                .find(|failure| failure.part == Some(part))
+                .map(|failure| Record::failed(result, failure))
+        });
+        day_failures.chain(parts).collect::<Vec<_>>()
+    })
+}
+
+/// Prints every record as a single JSON array once all days are done.
+pub struct JsonReporter;
+
+impl Reporter for JsonReporter {
+    fn finish(&mut self, results: &[DayResult]) {
+        println!("{}", render_json(results));
+    }
+}
+
+fn render_json(results: &[DayResult]) -> String {
+    let records: Vec<_> = records(results).collect();
+    serde_json::to_string_pretty(&records).expect("Records are always serializable")
+}
+
+/// Prints a CSV header, then one row per part as soon as its day is done.
+#[derive(Default)]
+pub struct CsvReporter {
+    header_printed: bool,
+}
+
+const CSV_HEADER: &str =
+    "year,day,part,answer,duration_ns,parse_ns,input_bytes,peak_bytes,allocations,error";
+
+impl Reporter for CsvReporter {
+    fn day_finished(&mut self, result: &DayResult) {
+        if !self.header_printed {
+            println!("{}", CSV_HEADER);
+            self.header_printed = true;
+        }
+        for record in records(std::slice::from_ref(result)) {
+            println!("{}", csv_row(&record));
+        }
+    }
+}
+
+fn csv_row(record: &Record) -> String {
+    let optional = |value: Option<String>| value.unwrap_or_default();
+    format!(
+        "{},{},{},{},{},{},{},{},{},{}",
+        record.year,
+        record.day,
+        optional(record.part.map(|part| part.to_string())),
+        optional(record.answer.map(csv_field)),
+        optional(record.duration_ns.map(|ns| ns.to_string())),
+        optional(record.parse_ns.map(|ns| ns.to_string())),
+        record.input_bytes,
+        optional(record.peak_bytes.map(|bytes| bytes.to_string())),
+        optional(record.allocations.map(|count| count.to_string())),
+        optional(record.error.map(csv_field)),
+    )
+}
+
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::memory::AllocStats;
+
+    fn sample_results() -> Vec<DayResult> {
+        vec![DayResult {
+            year: 2024,
+            day: 23,
+            input_bytes: 128,
+            parse_duration: Some(Duration::from_micros(5)),
+            part1: Some(PartResult {
+                answer: "7".into(),
+                duration: Duration::from_micros(12),
+                memory: None,
+            }),
+            part2: Some(PartResult {
+                answer: "co,de,ka,ta".into(),
+                duration: Duration::from_micros(34),
+                memory: None,
+            }),
+            failures: Vec::new(),
+        }]
+    }
+
+    #[test]
+    fn parses_formats() {
+        assert_eq!("json".parse::<Format>().unwrap(), Format::Json);
+        assert_eq!("csv".parse::<Format>().unwrap(), Format::Csv);
+        assert!("xml".parse::<Format>().is_err());
+    }
+
+    #[test]
+    fn json_has_one_record_per_part() {
+        let json: serde_json::Value =
+            serde_json::from_str(&render_json(&sample_results())).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!([
+                {"year": 2024, "day": 23, "part": 1, "answer": "7", "duration_ns": 12000, "parse_ns": 5000, "input_bytes": 128, "peak_bytes": null, "allocations": null, "error": null},
+                {"year": 2024, "day": 23, "part": 2, "answer": "co,de,ka,ta", "duration_ns": 34000, "parse_ns": 5000, "input_bytes": 128, "peak_bytes": null, "allocations": null, "error": null},
+            ])
+        );
+    }
+
+    #[test]
+    fn skipped_parts_have_no_record() {
+        let mut results = sample_results();
+        results[0].part1 = None;
+        let parts: Vec<_> = records(&results).map(|record| record.part).collect();
+        assert_eq!(parts, vec![Some(2)]);
+    }
+
+    #[test]
+    fn failures_are_recorded_with_their_error() {
+        let mut results = sample_results();
+        results[0].part2 = None;
+        results[0].failures.push(Failure {
+            year: 2024,
+            day: 23,
+            part: Some(2),
+            message: "panicked: boom".into(),
+        });
+
+        let json: serde_json::Value = serde_json::from_str(&render_json(&results)).unwrap();
+        assert_eq!(
+            json[1],
+            serde_json::json!({"year": 2024, "day": 23, "part": 2, "answer": null, "duration_ns": null, "parse_ns": 5000, "input_bytes": 128, "peak_bytes": null, "allocations": null, "error": "panicked: boom"})
+        );
+
+        let rows: Vec<_> = records(&results).map(|record| csv_row(&record)).collect();
+        assert_eq!(rows[1], "2024,23,2,,,5000,128,,,panicked: boom");
+    }
+
+    #[test]
+    fn csv_quotes_answers_with_commas() {
+        let rows: Vec<_> = records(&sample_results())
+            .map(|record| csv_row(&record))
+            .collect();
+        assert_eq!(rows[0], "2024,23,1,7,12000,5000,128,,,");
+        assert_eq!(rows[1], "2024,23,2,\"co,de,ka,ta\",34000,5000,128,,,");
+    }
+
+    #[test]
+    fn memory_is_recorded_when_measured() {
+        let mut results = sample_results();
+        results[0].part1.as_mut().unwrap().memory = Some(AllocStats {
+            peak_bytes: 4096,
+            allocations: 3,
+        });
+
+        let json: serde_json::Value = serde_json::from_str(&render_json(&results)).unwrap();
+        assert_eq!(json[0]["peak_bytes"], 4096);
+        assert_eq!(json[0]["allocations"], 3);
+        assert_eq!(json[1].get("peak_bytes"), Some(&serde_json::Value::Null));
+
+        let rows: Vec<_> = records(&results).map(|record| csv_row(&record)).collect();
+        assert_eq!(rows[0], "2024,23,1,7,12000,5000,128,4096,3,");
+    }
+}
@@ -0,0 +1,29 @@
+//! Tests generated by `build.rs`, one per sample input and part with a known answer.
+
+use crate::{
+    answers::Answers,
+    input::{self, InputSource},
+};
+
+fn check(year: u16, day: u8, sample: &str, part: u8) {
+    let sample_dir = input::sample_dir(year);
+    let input = InputSource::File(sample_dir.join(format!("{}.txt", sample)))
+        .read()
+        .unwrap();
+    let answers = Answers::load(&sample_dir.join(format!("{}.toml", sample))).unwrap();
+    let expected = answers
+        .expected(day, part)
+        .unwrap_or_else(|| panic!("{} has no answer for part {}", sample, part));
+
+    let registry = crate::registry();
+    let solution = registry
+        .get(year, day)
+        .unwrap_or_else(|| panic!("{} day {:02} is not registered", year, day));
+    let actual = solution
+        .answer(part, &input)
+        .unwrap_or_else(|e| panic!("{} part {} failed: {:#}", sample, part, e));
+
+    assert_eq!(actual, expected, "{} part {}", sample, part);
+}
+
+include!(concat!(env!("OUT_DIR"), "/sample_tests.rs"));
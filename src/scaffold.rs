@@ -0,0 +1,384 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{anyhow, Context, Result};
+
+use crate::{input, solution::DEFAULT_YEAR};
+
+/// Creates the module for a day, registers it, and adds an empty sample and answers file.
+///
+/// Days of [`DEFAULT_YEAR`] go in `src/days`, other years get a `src/yearYYYY` module that
+/// is created and registered in `lib.rs` along with its first day. Returns the files it
+/// created or changed. Nothing is written if the day already exists.
+pub fn scaffold(root: &Path, year: u16, day: u8) -> Result<Vec<PathBuf>> {
+    let module_dir = root.join("src").join(module_name(year));
+    let sample_dir = root.join(input::sample_dir(year));
+    let module_path = module_dir.join(format!("day{:02}.rs", day));
+    let mod_path = module_dir.join("mod.rs");
+    let lib_path = root.join("src/lib.rs");
+
+    if module_path.exists() {
+        return Err(anyhow!(
+            "{} already exists, refusing to overwrite it",
+            module_path.display()
+        ));
+    }
+    let (mod_rs, lib_rs) = if mod_path.exists() {
+        (register(&read(&mod_path)?, day)?, None)
+    } else {
+        (
+            year_module(year, day),
+            Some(register_year(&read(&lib_path)?, year)?),
+        )
+    };
+
+    fs::create_dir_all(&module_dir)
+        .with_context(|| format!("Failed to create {}", module_dir.display()))?;
+    let mut written = Vec::new();
+    write(&module_path, &template(year, day), &mut written)?;
+    write(&mod_path, &mod_rs, &mut written)?;
+    if let Some(lib_rs) = lib_rs {
+        write(&lib_path, &lib_rs, &mut written)?;
+    }
+
+    fs::create_dir_all(&sample_dir)
+        .with_context(|| format!("Failed to create {}", sample_dir.display()))?;
+    let sample_path = sample_dir.join(format!("sample{:02}.txt", day));
+    let answers_path = sample_dir.join(format!("sample{:02}.toml", day));
+    if !sample_path.exists() {
+        write(&sample_path, "", &mut written)?;
+    }
+    if !answers_path.exists() {
+        let answers = format!("[day{:02}]\n# part1 = \n# part2 = \n", day);
+        write(&answers_path, &answers, &mut written)?;
+    }
+    Ok(written)
+}
+
+fn module_name(year: u16) -> String {
+    if year == DEFAULT_YEAR {
+        "days".to_string()
+    } else {
+        format!("year{}", year)
+    }
+}
+
+fn read(path: &Path) -> Result<String> {
+    fs::read_to_string(path).with_context(|| format!("Failed to read {}", path.display()))
+}
+
+fn write(path: &Path, contents: &str, written: &mut Vec<PathBuf>) -> Result<()> {
+    fs::write(path, contents).with_context(|| format!("Failed to write {}", path.display()))?;
+    written.push(path.to_path_buf());
+    Ok(())
+}
+
+/// The `mod.rs` of a year that doesn't have any days yet.
+fn year_module(year: u16, day: u8) -> String {
+    format!(
+        "use crate::registry::Registry;\n\npub mod day{day:02};\n\npub use day{day:02}::Day{day:02};\n\n\
+         /// Registers the {year} solutions.\n\
+         pub fn register(registry: &mut Registry) {{\n    registry.register(Day{day:02});\n}}\n"
+    )
+}
+
+/// Declares a new year module in `lib.rs` and adds it to the registry.
+fn register_year(lib_rs: &str, year: u16) -> Result<String> {
+    let name = module_name(year);
+    if lib_rs.contains(&format!("mod {};", name)) {
+        return Err(anyhow!("{} is already declared in lib.rs", name));
+    }
+
+    let mut lines: Vec<String> = lib_rs.lines().map(String::from).collect();
+    insert_after_last(
+        &mut lines,
+        |line| line == "pub mod days;" || (line.starts_with("pub mod year") && line.ends_with(';')),
+        format!("pub mod {};", name),
+    )?;
+    insert_after_last(
+        &mut lines,
+        |line| line.trim_start().ends_with("::register(&mut registry);"),
+        format!("    {}::register(&mut registry);", name),
+    )?;
+
+    let mut out = lines.join("\n");
+    out.push('\n');
+    Ok(out)
+}
+
+fn insert_after_last(
+    lines: &mut Vec<String>,
+    matches: impl Fn(&str) -> bool,
+    line: String,
+) -> Result<()> {
+    let index = lines
+        .iter()
+        .rposition(|existing| matches(existing))
+        .ok_or(anyhow!("Nowhere to add `{}` to lib.rs", line.trim()))?;
+    lines.insert(index + 1, line);
+    Ok(())
+}
+
+/// Adds the module declaration, re-export and registry entry for `day` to `days/mod.rs`.
+fn register(mod_rs: &str, day: u8) -> Result<String> {
+    let name = format!("day{:02}", day);
+    let ty = format!("Day{:02}", day);
+    if mod_rs.contains(&format!("mod {};", name)) {
+        return Err(anyhow!("{} is already declared in days/mod.rs", name));
+    }
+
+    let mut lines: Vec<String> = mod_rs.lines().map(String::from).collect();
+    insert_sorted(
+        &mut lines,
+        "pub mod day",
+        ";",
+        day,
+        format!("pub mod {};", name),
+    )?;
+    insert_sorted(
+        &mut lines,
+        "pub use day",
+        "",
+        day,
+        format!("pub use {}::{};", name, ty),
+    )?;
+    insert_sorted(
+        &mut lines,
+        "    registry.register(Day",
+        ");",
+        day,
+        format!("    registry.register({});", ty),
+    )?;
+
+    let mut out = lines.join("\n");
+    out.push('\n');
+    Ok(out)
+}
+
+/// Inserts `line` among the lines that start with `prefix` followed by a day number,
+/// keeping them ordered by day.
+fn insert_sorted(
+    lines: &mut Vec<String>,
+    prefix: &str,
+    suffix: &str,
+    day: u8,
+    line: String,
+) -> Result<()> {
+    let numbered: Vec<(usize, u8)> = lines
+        .iter()
+        .enumerate()
+        .filter_map(|(i, existing)| {
+            let rest = existing.strip_prefix(prefix)?;
+            let number = rest.get(..2)?.parse().ok()?;
+            rest[2..].starts_with(suffix).then_some((i, number))
+        })
+        .collect();
+
+    let index = match numbered.iter().find(|&&(_, existing)| existing > day) {
+        Some(&(i, _)) => i,
+        None => match numbered.last() {
+            Some(&(i, _)) => i + 1,
+            None => {
+                return Err(anyhow!(
+                    "No `{}NN` lines to register the day next to",
+                    prefix
+                ))
+            }
+        },
+    };
+    lines.insert(index, line);
+    Ok(())
+}
+
+fn template(year: u16, day: u8) -> String {
+    let year_fn = if year == DEFAULT_YEAR {
+        String::new()
+    } else {
+        format!("\n    fn year(&self) -> u16 {{\n        {}\n    }}\n", year)
+    };
+    TEMPLATE
+        .replace("{day}", &day.to_string())
+        .replace("{year_fn}", &year_fn)
+        .replace("{name}", &format!("Day{:02}", day))
+}
+
+const TEMPLATE: &str = r#"use std::str::FromStr;
+
+use anyhow::{anyhow, Result};
+
+use crate::solution::Solution;
+
+pub struct {name};
+
+impl Solution for {name} {
+    type Answer = usize;
+    type Parsed<'a> = Puzzle;
+    fn day(&self) -> u8 {
+        {day}
+    }
+{year_fn}
+    fn parse(input: &str) -> Result<Self::Parsed<'_>> {
+        input.parse()
+    }
+
+    fn part1(puzzle: &Puzzle) -> Result<Self::Answer> {
+        Err(anyhow!("not solved yet ({} lines)", puzzle.lines.len()))
+    }
+
+    fn part2(puzzle: &Puzzle) -> Result<Self::Answer> {
+        Err(anyhow!("not solved yet ({} lines)", puzzle.lines.len()))
+    }
+}
+
+pub struct Puzzle {
+    lines: Vec<String>,
+}
+
+impl FromStr for Puzzle {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Self {
+            lines: s.lines().map(String::from).collect(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[ignore = "fill in the sample answer"]
+    fn part1() {
+        let sample1 = {name}.run_test1();
+        assert_eq!(sample1, 0);
+    }
+
+    #[test]
+    #[ignore = "fill in the sample answer"]
+    fn part2() {
+        let sample2 = {name}.run_test2();
+        assert_eq!(sample2, 0);
+    }
+}
+"#;
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+
+    use super::*;
+
+    const MOD_RS: &str = "use crate::registry::Registry;
+
+pub mod day01;
+pub mod day03;
+
+pub use day01::Day01;
+pub use day03::Day03;
+
+pub fn register(registry: &mut Registry) {
+    registry.register(Day01);
+    registry.register(Day03);
+}
+";
+
+    const LIB_RS: &str = "pub mod days;
+
+pub mod registry;
+
+pub fn registry() -> registry::Registry {
+    let mut registry = registry::Registry::default();
+    days::register(&mut registry);
+    registry
+}
+";
+
+    #[test]
+    fn registers_in_day_order() {
+        let registered = register(MOD_RS, 2).unwrap();
+        assert!(registered.contains("pub mod day01;\npub mod day02;\npub mod day03;\n"));
+        assert!(registered
+            .contains("pub use day01::Day01;\npub use day02::Day02;\npub use day03::Day03;\n"));
+        assert!(registered.contains(
+            "    registry.register(Day01);\n    registry.register(Day02);\n    registry.register(Day03);\n"
+        ));
+
+        let appended = register(MOD_RS, 4).unwrap();
+        assert!(
+            appended.contains("    registry.register(Day03);\n    registry.register(Day04);\n}\n")
+        );
+    }
+
+    #[test]
+    fn refuses_registered_days() {
+        assert!(register(MOD_RS, 3).is_err());
+    }
+
+    #[test]
+    fn template_names_the_day() {
+        let module = template(DEFAULT_YEAR, 7);
+        assert!(module.contains("pub struct Day07;"));
+        assert!(module.contains("fn day(&self) -> u8 {\n        7\n    }"));
+        assert!(module.contains("Day07.run_test1()"));
+        assert!(!module.contains("todo!"));
+        assert!(!module.contains("fn year"));
+
+        let module = template(2023, 7);
+        assert!(module.contains("fn year(&self) -> u16 {\n        2023\n    }"));
+    }
+
+    #[test]
+    fn registers_new_years() {
+        let lib_rs = register_year(LIB_RS, 2023).unwrap();
+        assert!(lib_rs.starts_with("pub mod days;\npub mod year2023;\n"));
+        assert!(lib_rs.contains(
+            "    days::register(&mut registry);\n    year2023::register(&mut registry);\n"
+        ));
+
+        let lib_rs = register_year(&lib_rs, 2022).unwrap();
+        assert!(lib_rs.starts_with("pub mod days;\npub mod year2023;\npub mod year2022;\n"));
+        assert!(register_year(&lib_rs, 2022).is_err());
+    }
+
+    #[test]
+    fn refuses_to_overwrite_modules() {
+        let root = env::temp_dir().join(format!("aoc2024-scaffold-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(root.join("src/days")).unwrap();
+        fs::create_dir_all(root.join("src/input")).unwrap();
+        fs::write(root.join("src/days/mod.rs"), MOD_RS).unwrap();
+        fs::write(root.join("src/lib.rs"), LIB_RS).unwrap();
+        fs::write(root.join("src/days/day03.rs"), "// solved").unwrap();
+
+        assert!(scaffold(&root, DEFAULT_YEAR, 3).is_err());
+        assert_eq!(
+            fs::read_to_string(root.join("src/days/day03.rs")).unwrap(),
+            "// solved"
+        );
+
+        let written = scaffold(&root, DEFAULT_YEAR, 2).unwrap();
+        assert_eq!(written.len(), 4);
+        assert!(fs::read_to_string(root.join("src/days/mod.rs"))
+            .unwrap()
+            .contains("registry.register(Day02);"));
+        assert!(scaffold(&root, DEFAULT_YEAR, 2).is_err());
+
+        let written = scaffold(&root, 2023, 5).unwrap();
+        assert_eq!(written.len(), 5);
+        assert!(root.join("src/year2023/day05.rs").is_file());
+        assert!(root.join("src/input/2023/sample05.toml").is_file());
+        assert!(fs::read_to_string(root.join("src/lib.rs"))
+            .unwrap()
+            .contains("year2023::register(&mut registry);"));
+
+        scaffold(&root, 2023, 1).unwrap();
+        assert!(fs::read_to_string(root.join("src/year2023/mod.rs"))
+            .unwrap()
+            .contains("pub mod day01;\npub mod day05;\n"));
+        fs::remove_dir_all(root).unwrap();
+    }
+}
@@ -0,0 +1,594 @@
+//! Graph searches over implicit graphs.
+//!
+//! States can be anything hashable, the graph is given by a neighbor function: `bfs` takes
+//! one yielding the next states, the others one yielding `(state, cost)` pairs. Every search
+//! accepts several start states, and costs must not be negative.
+
+use std::{
+    cmp::Ordering,
+    collections::{BinaryHeap, HashMap, HashSet, VecDeque},
+    hash::Hash,
+    ops::Add,
+};
+
+/// A path cost: summable, comparable, and zero by default.
+pub trait Cost: Copy + Ord + Add<Output = Self> + Default {}
+
+impl<T: Copy + Ord + Add<Output = T> + Default> Cost for T {}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Path<S, C> {
+    /// Every state from the start to the goal, both included.
+    pub states: Vec<S>,
+    pub cost: C,
+}
+
+/// A state waiting in a priority queue, popped cheapest first.
+struct Queued<S, C> {
+    priority: C,
+    cost: C,
+    state: S,
+}
+
+impl<S, C: Ord> PartialEq for Queued<S, C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.priority == other.priority
+    }
+}
+
+impl<S, C: Ord> Eq for Queued<S, C> {}
+
+impl<S, C: Ord> PartialOrd for Queued<S, C> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<S, C: Ord> Ord for Queued<S, C> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.priority.cmp(&self.priority)
+    }
+}
+
+/// Follows `parents` back from `state` to the start it was reached from.
+fn reconstruct<S: Clone + Eq + Hash>(parents: &HashMap<S, S>, state: S) -> Vec<S> {
+    let mut states = vec![state];
+    while let Some(parent) = parents.get(states.last().unwrap()) {
+        states.push(parent.clone());
+    }
+    states.reverse();
+    states
+}
+
+/// The path with the fewest steps to a goal, the cost being the number of steps.
+pub fn bfs<S, I>(
+    starts: impl IntoIterator<Item = S>,
+    mut neighbors: impl FnMut(&S) -> I,
+    mut is_goal: impl FnMut(&S) -> bool,
+) -> Option<Path<S, usize>>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = S>,
+{
+    let mut parents = HashMap::new();
+    let mut seen = HashSet::new();
+    let mut queue = VecDeque::new();
+    for start in starts {
+        if seen.insert(start.clone()) {
+            queue.push_back((start, 0));
+        }
+    }
+
+    while let Some((state, steps)) = queue.pop_front() {
+        if is_goal(&state) {
+            return Some(Path {
+                states: reconstruct(&parents, state),
+                cost: steps,
+            });
+        }
+
+        for next in neighbors(&state) {
+            if seen.insert(next.clone()) {
+                parents.insert(next.clone(), state.clone());
+                queue.push_back((next, steps + 1));
+            }
+        }
+    }
+
+    None
+}
+
+/// The cheapest path to a goal.
+pub fn dijkstra<S, C, I>(
+    starts: impl IntoIterator<Item = S>,
+    neighbors: impl FnMut(&S) -> I,
+    is_goal: impl FnMut(&S) -> bool,
+) -> Option<Path<S, C>>
+where
+    S: Clone + Eq + Hash,
+    C: Cost,
+    I: IntoIterator<Item = (S, C)>,
+{
+    astar(starts, neighbors, |_| C::default(), is_goal)
+}
+
+/// The cheapest path to a goal, exploring the states `heuristic` estimates closest first.
+///
+/// The heuristic must never overestimate the remaining cost, or the path found may not be
+/// the cheapest one.
+pub fn astar<S, C, I>(
+    starts: impl IntoIterator<Item = S>,
+    mut neighbors: impl FnMut(&S) -> I,
+    mut heuristic: impl FnMut(&S) -> C,
+    mut is_goal: impl FnMut(&S) -> bool,
+) -> Option<Path<S, C>>
+where
+    S: Clone + Eq + Hash,
+    C: Cost,
+    I: IntoIterator<Item = (S, C)>,
+{
+    let mut costs = HashMap::new();
+    let mut parents = HashMap::new();
+    let mut queue = BinaryHeap::new();
+    for start in starts {
+        costs.insert(start.clone(), C::default());
+        queue.push(Queued {
+            priority: heuristic(&start),
+            cost: C::default(),
+            state: start,
+        });
+    }
+
+    while let Some(Queued { cost, state, .. }) = queue.pop() {
+        if costs.get(&state).is_some_and(|&best| best < cost) {
+            continue;
+        }
+        if is_goal(&state) {
+            return Some(Path {
+                states: reconstruct(&parents, state),
+                cost,
+            });
+        }
+
+        for (next, step) in neighbors(&state) {
+            let next_cost = cost + step;
+            if costs.get(&next).is_some_and(|&best| best <= next_cost) {
+                continue;
+            }
+            costs.insert(next.clone(), next_cost);
+            parents.insert(next.clone(), state.clone());
+            queue.push(Queued {
+                priority: next_cost + heuristic(&next),
+                cost: next_cost,
+                state: next,
+            });
+        }
+    }
+
+    None
+}
+
+/// One side of a bidirectional search.
+struct Frontier<S, C> {
+    costs: HashMap<S, C>,
+    parents: HashMap<S, S>,
+    queue: BinaryHeap<Queued<S, C>>,
+}
+
+impl<S: Clone + Eq + Hash, C: Cost> Frontier<S, C> {
+    fn new(starts: impl IntoIterator<Item = S>) -> Self {
+        let mut frontier = Frontier {
+            costs: HashMap::new(),
+            parents: HashMap::new(),
+            queue: BinaryHeap::new(),
+        };
+        for start in starts {
+            frontier.costs.insert(start.clone(), C::default());
+            frontier.queue.push(Queued {
+                priority: C::default(),
+                cost: C::default(),
+                state: start,
+            });
+        }
+        frontier
+    }
+
+    fn next_cost(&self) -> Option<C> {
+        self.queue.peek().map(|queued| queued.cost)
+    }
+
+    /// Expands the cheapest state, updating `best` when it connects to `other`'s side.
+    fn expand<I>(
+        &mut self,
+        other: &Self,
+        neighbors: &mut impl FnMut(&S) -> I,
+        best: &mut Option<(C, S)>,
+    ) where
+        I: IntoIterator<Item = (S, C)>,
+    {
+        let Some(Queued { cost, state, .. }) = self.queue.pop() else {
+            return;
+        };
+        if self.costs.get(&state).is_some_and(|&known| known < cost) {
+            return;
+        }
+
+        for (next, step) in neighbors(&state) {
+            let next_cost = cost + step;
+            if self
+                .costs
+                .get(&next)
+                .is_some_and(|&known| known <= next_cost)
+            {
+                continue;
+            }
+            self.costs.insert(next.clone(), next_cost);
+            self.parents.insert(next.clone(), state.clone());
+            if let Some(&remaining) = other.costs.get(&next) {
+                let total = next_cost + remaining;
+                if best.as_ref().is_none_or(|(cost, _)| total < *cost) {
+                    *best = Some((total, next.clone()));
+                }
+            }
+            self.queue.push(Queued {
+                priority: next_cost,
+                cost: next_cost,
+                state: next,
+            });
+        }
+    }
+}
+
+/// The cheapest path from any of `starts` to `goal`, searching from both ends until they meet.
+///
+/// `forward` yields the states reachable from a state, `backward` the states a state can be
+/// reached from. On undirected graphs they are the same function.
+pub fn bidirectional<S, C, I, J>(
+    starts: impl IntoIterator<Item = S>,
+    goal: S,
+    mut forward: impl FnMut(&S) -> I,
+    mut backward: impl FnMut(&S) -> J,
+) -> Option<Path<S, C>>
+where
+    S: Clone + Eq + Hash,
+    C: Cost,
+    I: IntoIterator<Item = (S, C)>,
+    J: IntoIterator<Item = (S, C)>,
+{
+    let mut from_start = Frontier::new(starts);
+    if from_start.costs.contains_key(&goal) {
+        return Some(Path {
+            states: vec![goal],
+            cost: C::default(),
+        });
+    }
+    let mut from_goal = Frontier::new([goal]);
+    let mut best: Option<(C, S)> = None;
+
+    // Once either side runs out every state it can reach has been settled, and with it
+    // every path through them.
+    while let (Some(start_cost), Some(goal_cost)) = (from_start.next_cost(), from_goal.next_cost())
+    {
+        if best
+            .as_ref()
+            .is_some_and(|(cost, _)| start_cost + goal_cost >= *cost)
+        {
+            break;
+        }
+        if start_cost <= goal_cost {
+            from_start.expand(&from_goal, &mut forward, &mut best);
+        } else {
+            from_goal.expand(&from_start, &mut backward, &mut best);
+        }
+    }
+
+    let (cost, meeting) = best?;
+    let mut states = reconstruct(&from_start.parents, meeting.clone());
+    let mut to_goal = reconstruct(&from_goal.parents, meeting);
+    to_goal.reverse();
+    states.extend(to_goal.into_iter().skip(1));
+    Some(Path { states, cost })
+}
+
+/// Every cheapest path to the nearest goals, as a DAG of predecessors.
+///
+/// Free steps that lead back to a state already on the way, through a zero-cost loop, are
+/// left out: they cost nothing but would make the predecessors go round in circles.
+#[derive(Debug, Clone)]
+pub struct ShortestPaths<S, C> {
+    costs: HashMap<S, C>,
+    predecessors: HashMap<S, Vec<S>>,
+    goals: Vec<S>,
+}
+
+/// Explores the graph like [`dijkstra`], but remembers every cheapest way into a state and
+/// keeps going until all the goals reachable at the lowest cost are found.
+pub fn all_shortest_paths<S, C, I>(
+    starts: impl IntoIterator<Item = S>,
+    mut neighbors: impl FnMut(&S) -> I,
+    mut is_goal: impl FnMut(&S) -> bool,
+) -> ShortestPaths<S, C>
+where
+    S: Clone + Eq + Hash,
+    C: Cost,
+    I: IntoIterator<Item = (S, C)>,
+{
+    let mut costs = HashMap::new();
+    let mut predecessors: HashMap<S, Vec<S>> = HashMap::new();
+    let mut goals = Vec::new();
+    let mut goal_cost = None;
+    let mut queue = BinaryHeap::new();
+    let starts: HashSet<S> = starts.into_iter().collect();
+    for start in &starts {
+        costs.insert(start.clone(), C::default());
+        queue.push(Queued {
+            priority: C::default(),
+            cost: C::default(),
+            state: start.clone(),
+        });
+    }
+
+    while let Some(Queued { cost, state, .. }) = queue.pop() {
+        if costs.get(&state).is_some_and(|&best| best < cost) {
+            continue;
+        }
+        if goal_cost.is_some_and(|goal_cost| cost > goal_cost) {
+            break;
+        }
+        if is_goal(&state) {
+            goal_cost = Some(cost);
+            goals.push(state.clone());
+        }
+
+        for (next, step) in neighbors(&state) {
+            let next_cost = cost + step;
+            match costs.get(&next) {
+                Some(&best) if best < next_cost => continue,
+                Some(&best) if best == next_cost => {
+                    // Only a free step can lead back to a state on the way here.
+                    let loops = starts.contains(&next)
+                        || (step == C::default() && leads_to(&predecessors, &next, &state));
+                    if !loops {
+                        predecessors.entry(next).or_default().push(state.clone());
+                    }
+                    continue;
+                }
+                _ => {}
+            }
+            costs.insert(next.clone(), next_cost);
+            predecessors.insert(next.clone(), vec![state.clone()]);
+            queue.push(Queued {
+                priority: next_cost,
+                cost: next_cost,
+                state: next,
+            });
+        }
+    }
+
+    ShortestPaths {
+        costs,
+        predecessors,
+        goals,
+    }
+}
+
+/// Whether `ancestor` is `state` or one of the states on the recorded ways into it.
+fn leads_to<S: Eq + Hash>(predecessors: &HashMap<S, Vec<S>>, ancestor: &S, state: &S) -> bool {
+    let mut seen = HashSet::new();
+    let mut edge = vec![state];
+    while let Some(current) = edge.pop() {
+        if current == ancestor {
+            return true;
+        }
+        for predecessor in predecessors.get(current).into_iter().flatten() {
+            if seen.insert(predecessor) {
+                edge.push(predecessor);
+            }
+        }
+    }
+    false
+}
+
+impl<S: Clone + Eq + Hash, C: Cost> ShortestPaths<S, C> {
+    /// The cost of the cheapest path to a goal, if one was reachable.
+    pub fn cost(&self) -> Option<C> {
+        self.goals.first().map(|goal| self.costs[goal])
+    }
+
+    /// The goals reached at the lowest cost.
+    pub fn goals(&self) -> &[S] {
+        &self.goals
+    }
+
+    /// The cheapest cost found for `state`.
+    ///
+    /// Only final for states no more expensive than the goals, the search stops there.
+    pub fn cost_to(&self, state: &S) -> Option<C> {
+        self.costs.get(state).copied()
+    }
+
+    /// The states `state` is reached from on its cheapest paths, empty for the starts.
+    pub fn predecessors(&self, state: &S) -> &[S] {
+        self.predecessors.get(state).map_or(&[], Vec::as_slice)
+    }
+
+    /// One of the cheapest paths to a goal.
+    pub fn path(&self) -> Option<Path<S, C>> {
+        let goal = self.goals.first()?;
+        let mut states = vec![goal.clone()];
+        while let Some(predecessor) = self.predecessors(states.last().unwrap()).first() {
+            states.push(predecessor.clone());
+        }
+        states.reverse();
+        Some(Path {
+            states,
+            cost: self.costs[goal],
+        })
+    }
+
+    /// Every state on any of the cheapest paths to a goal.
+    pub fn states_on_paths(&self) -> HashSet<S> {
+        let mut states: HashSet<S> = self.goals.iter().cloned().collect();
+        let mut edge = self.goals.clone();
+        while let Some(state) = edge.pop() {
+            for predecessor in self.predecessors(&state) {
+                if states.insert(predecessor.clone()) {
+                    edge.push(predecessor.clone());
+                }
+            }
+        }
+        states
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A weighted, directed graph:
+    ///
+    /// ```text
+    /// a -1-> b -1-> d -5-> e
+    /// a -2-> c -0-> d
+    /// a -9-> e
+    /// ```
+    fn edges(state: &char) -> Vec<(char, u32)> {
+        match state {
+            'a' => vec![('b', 1), ('c', 2), ('e', 9)],
+            'b' => vec![('d', 1)],
+            'c' => vec![('d', 0)],
+            'd' => vec![('e', 5)],
+            _ => vec![],
+        }
+    }
+
+    fn reverse_edges(state: &char) -> Vec<(char, u32)> {
+        ['a', 'b', 'c', 'd', 'e']
+            .into_iter()
+            .flat_map(|from| {
+                edges(&from)
+                    .into_iter()
+                    .filter(|(to, _)| to == state)
+                    .map(move |(_, cost)| (from, cost))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn bfs_counts_steps() {
+        let unweighted = |state: &char| edges(state).into_iter().map(|(next, _)| next);
+        let path = bfs(['a'], unweighted, |&state| state == 'd').unwrap();
+        assert_eq!(path.cost, 2);
+        assert_eq!(path.states, vec!['a', 'b', 'd']);
+
+        assert_eq!(
+            bfs(['a'], unweighted, |&state| state == 'e').unwrap().cost,
+            1
+        );
+        assert_eq!(
+            bfs(['c', 'b'], unweighted, |&state| state == 'c')
+                .unwrap()
+                .cost,
+            0
+        );
+        assert!(bfs(['e'], unweighted, |&state| state == 'a').is_none());
+    }
+
+    #[test]
+    fn dijkstra_finds_the_cheapest_path() {
+        let path = dijkstra(['a'], edges, |&state| state == 'e').unwrap();
+        assert_eq!(path.cost, 7);
+        assert_eq!(path.states, vec!['a', 'b', 'd', 'e']);
+
+        let path = dijkstra(['c', 'b'], edges, |&state| state == 'e').unwrap();
+        assert_eq!(path.states, vec!['c', 'd', 'e']);
+        assert_eq!(path.cost, 5);
+
+        assert!(dijkstra(['e'], edges, |&state| state == 'a').is_none());
+    }
+
+    #[test]
+    fn astar_on_a_grid() {
+        let size = 10;
+        let wall = |(x, y): (i32, i32)| x == 5 && y < 8;
+        let neighbors = |&(x, y): &(i32, i32)| {
+            [(x + 1, y), (x - 1, y), (x, y + 1), (x, y - 1)]
+                .into_iter()
+                .filter(|&(x, y)| x >= 0 && y >= 0 && x < size && y < size)
+                .filter(|&position| !wall(position))
+                .map(|position| (position, 1))
+        };
+        let goal = (9, 0);
+        let distance = |&(x, y): &(i32, i32)| (goal.0 - x).abs() + (goal.1 - y).abs();
+
+        let path = astar([(0, 0)], neighbors, distance, |&state| state == goal).unwrap();
+        let expected = dijkstra([(0, 0)], neighbors, |&state| state == goal).unwrap();
+        assert_eq!(path.cost, 25);
+        assert_eq!(path.cost, expected.cost);
+        assert_eq!(path.states.len(), 26);
+    }
+
+    #[test]
+    fn bidirectional_meets_in_the_middle() {
+        let path = bidirectional(['a'], 'e', edges, reverse_edges).unwrap();
+        assert_eq!(path.cost, 7);
+        assert_eq!(path.states.first(), Some(&'a'));
+        assert_eq!(path.states.last(), Some(&'e'));
+        assert_eq!(path.states.len(), 4);
+
+        let path = bidirectional(['c'], 'e', edges, reverse_edges).unwrap();
+        assert_eq!(path.states, vec!['c', 'd', 'e']);
+
+        let path = bidirectional(['a', 'b'], 'e', edges, reverse_edges).unwrap();
+        assert_eq!(path.states, vec!['b', 'd', 'e']);
+        assert_eq!(path.cost, 6);
+
+        assert_eq!(
+            bidirectional(['b', 'a'], 'a', edges, reverse_edges)
+                .unwrap()
+                .cost,
+            0
+        );
+        assert!(bidirectional(['e'], 'a', edges, reverse_edges).is_none());
+        assert!(bidirectional([], 'a', edges, reverse_edges).is_none());
+    }
+
+    #[test]
+    fn all_shortest_paths_keeps_every_predecessor() {
+        let paths = all_shortest_paths(['a'], edges, |&state| state == 'e');
+        assert_eq!(paths.cost(), Some(7));
+        assert_eq!(paths.goals(), &['e']);
+        assert_eq!(paths.cost_to(&'d'), Some(2));
+
+        let mut into_d = paths.predecessors(&'d').to_vec();
+        into_d.sort();
+        assert_eq!(into_d, vec!['b', 'c']);
+        assert!(paths.predecessors(&'a').is_empty());
+
+        let mut on_paths: Vec<_> = paths.states_on_paths().into_iter().collect();
+        on_paths.sort();
+        assert_eq!(on_paths, vec!['a', 'b', 'c', 'd', 'e']);
+        assert_eq!(paths.path().unwrap().cost, 7);
+
+        let unreachable = all_shortest_paths(['e'], edges, |&state| state == 'a');
+        assert_eq!(unreachable.cost(), None);
+        assert!(unreachable.states_on_paths().is_empty());
+    }
+
+    #[test]
+    fn all_shortest_paths_survives_zero_cost_cycles() {
+        // a -0-> b -0-> a, and b -0-> c -0-> b on the way to d.
+        let edges = |state: &char| match state {
+            'a' => vec![('b', 0)],
+            'b' => vec![('a', 0), ('c', 0)],
+            'c' => vec![('b', 0), ('d', 1)],
+            _ => vec![],
+        };
+        let paths = all_shortest_paths(['a'], edges, |&state| state == 'd');
+        assert!(paths.predecessors(&'a').is_empty());
+        assert_eq!(paths.predecessors(&'b'), &['a']);
+        assert_eq!(paths.predecessors(&'c'), &['b']);
+        assert_eq!(paths.path().unwrap().states, vec!['a', 'b', 'c', 'd']);
+        assert_eq!(paths.states_on_paths().len(), 4);
+    }
+}
@@ -1,58 +1,673 @@
 use std::{
-    fmt::{Debug, Display},
-    fs,
-    time::Instant,
+    any::Any,
+    fmt::{Debug, Display, Formatter},
+    panic::{self, AssertUnwindSafe},
+    str::FromStr,
+    sync::{
+        mpsc::{self, RecvTimeoutError},
+        Arc, Mutex,
+    },
+    thread,
+    time::{Duration, Instant},
 };
 
-use anyhow::Result;
+use anyhow::{anyhow, Result};
+
+use crate::{
+    animate::{self, Animate, FrameSink},
+    bench::{self, DayBench},
+    input::InputSource,
+    memory::{self, AllocStats},
+    profile::{self, DayProfile, PartProfile},
+    report::Reporter,
+};
+
+/// The year of the original `days` module, used whenever no year is given.
+pub const DEFAULT_YEAR: u16 = 2024;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Parts {
+    One,
+    Two,
+    #[default]
+    Both,
+}
+
+impl Parts {
+    pub fn includes(&self, part: u8) -> bool {
+        matches!(
+            (self, part),
+            (Parts::One, 1) | (Parts::Two, 2) | (Parts::Both, 1 | 2)
+        )
+    }
+}
+
+impl FromStr for Parts {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "1" => Ok(Parts::One),
+            "2" => Ok(Parts::Two),
+            "both" => Ok(Parts::Both),
+            _ => Err(anyhow!("Invalid part: {}, expected 1, 2 or both", s)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct RunOptions {
+    pub parts: Parts,
+    pub timeout: Option<Duration>,
+}
+
+#[derive(Debug, Clone)]
+pub struct PartResult {
+    pub answer: String,
+    pub duration: Duration,
+    /// Only measured with the `alloc-stats` feature.
+    pub memory: Option<AllocStats>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Failure {
+    pub year: u16,
+    pub day: u8,
+    /// `None` when the day failed before any part ran, e.g. while reading its input.
+    pub part: Option<u8>,
+    pub message: String,
+}
+
+impl Display for Failure {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self.part {
+            Some(part) => write!(f, "Day {:02} part {}: {}", self.day, part, self.message),
+            None => write!(f, "Day {:02}: {}", self.day, self.message),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct DayResult {
+    pub year: u16,
+    pub day: u8,
+    pub input_bytes: usize,
+    /// How long parsing took, if the day got that far.
+    pub parse_duration: Option<Duration>,
+    pub part1: Option<PartResult>,
+    pub part2: Option<PartResult>,
+    pub failures: Vec<Failure>,
+}
+
+impl DayResult {
+    pub fn part(&self, part: u8) -> Option<&PartResult> {
+        match part {
+            1 => self.part1.as_ref(),
+            2 => self.part2.as_ref(),
+            _ => None,
+        }
+    }
+
+    pub fn failure(&self, part: u8) -> Option<&Failure> {
+        self.failures
+            .iter()
+            .find(|failure| failure.part.is_none_or(|p| p == part))
+    }
+}
+
+/// What the runner hears back while solving a day, in order: the parse, then each part.
+#[derive(Debug)]
+pub enum Phase {
+    Parsed(Result<Duration>),
+    Solved(u8, Result<PartResult>),
+}
 
 pub trait Solution {
     type Answer: Debug + Display + Clone + PartialEq;
+    /// The parsed input, shared by both parts. Days that solve straight from the text use
+    /// `&'a str` and return the input as is from [`Solution::parse`].
+    type Parsed<'a>;
     fn day(&self) -> u8;
-    fn part1(input: &str) -> Result<Self::Answer>;
-    fn part2(input: &str) -> Result<Self::Answer>;
+    fn year(&self) -> u16 {
+        DEFAULT_YEAR
+    }
+    fn parse(input: &str) -> Result<Self::Parsed<'_>>;
+    fn part1(parsed: &Self::Parsed<'_>) -> Result<Self::Answer>;
+    fn part2(parsed: &Self::Parsed<'_>) -> Result<Self::Answer>;
 
-    fn solve(input: &str) -> Result<()> {
+    fn part(part: u8, parsed: &Self::Parsed<'_>) -> Result<Self::Answer> {
+        match part {
+            1 => Self::part1(parsed),
+            2 => Self::part2(parsed),
+            _ => Err(anyhow!("Invalid part: {}", part)),
+        }
+    }
+
+    /// The simulation behind `part`, for days that can be watched step by step.
+    fn animation<'a>(_part: u8, _parsed: &'a Self::Parsed<'_>) -> Option<Box<dyn Animate + 'a>> {
+        None
+    }
+
+    /// Parses the input and solves a single part of it.
+    fn solve(part: u8, input: &str) -> Result<Self::Answer> {
+        Self::part(part, &Self::parse(input)?)
+    }
+
+    /// Solves a single part, turning a panic inside it into an error.
+    fn solve_part(part: u8, parsed: &Self::Parsed<'_>) -> Result<PartResult> {
         let start = Instant::now();
-        let part1 = Self::part1(input)?;
-        println!("Part 1 solution: {}, took: {:?}", part1, start.elapsed());
+        let (answer, memory) = memory::track(|| catch_panic(|| Self::part(part, parsed)));
+        let duration = start.elapsed();
+
+        Ok(PartResult {
+            answer: answer?.to_string(),
+            duration,
+            memory,
+        })
+    }
 
+    /// Parses the input once and solves `parts` with it, handing each phase to `emit` as it
+    /// finishes. Stops early if parsing fails or `emit` returns `false`.
+    fn solve_phases(input: &str, parts: &[u8], emit: &mut dyn FnMut(Phase) -> bool) {
         let start = Instant::now();
-        let part2 = Self::part2(input)?;
-        println!("Part 2 solution: {}, took: {:?}", part2, start.elapsed());
-        println!();
+        let parsed = match catch_panic(|| Self::parse(input)) {
+            Ok(parsed) => parsed,
+            Err(err) => {
+                emit(Phase::Parsed(Err(err)));
+                return;
+            }
+        };
+        if !emit(Phase::Parsed(Ok(start.elapsed()))) {
+            return;
+        }
 
-        Ok(())
+        for &part in parts {
+            if !emit(Phase::Solved(part, Self::solve_part(part, &parsed))) {
+                return;
+            }
+        }
     }
 
-    fn run(&self) -> Result<()> {
-        let day = self.day();
-        let path = format!("./src/input/day{:02}.txt", day);
+    /// Like [`Solution::solve_phases`], but on a worker thread, giving up on any phase that
+    /// takes longer than `timeout`.
+    ///
+    /// A phase that times out cannot be cancelled, its thread keeps running in the background
+    /// until it finishes or the process exits. The parts after it are solved on a fresh worker,
+    /// which parses the input again without reporting it twice.
+    fn solve_phases_with_timeout(
+        input: Arc<str>,
+        mut parts: Vec<u8>,
+        timeout: Duration,
+        emit: &mut dyn FnMut(Phase),
+    ) where
+        Self: 'static,
+    {
+        let mut parse_reported = false;
+        while !parts.is_empty() {
+            let (sender, receiver) = mpsc::channel();
+            let worker_input = input.clone();
+            let worker_parts = parts.clone();
+            let spawned = thread::Builder::new()
+                .name("solver".to_string())
+                .spawn(move || {
+                    // The receiver is gone if we already timed out, nothing left to report to.
+                    Self::solve_phases(&worker_input, &worker_parts, &mut |phase| {
+                        sender.send(phase).is_ok()
+                    })
+                });
+            if let Err(err) = spawned {
+                emit(Phase::Parsed(Err(err.into())));
+                return;
+            }
+
+            match receiver.recv_timeout(timeout) {
+                Ok(Phase::Parsed(Ok(duration))) => {
+                    if !parse_reported {
+                        emit(Phase::Parsed(Ok(duration)));
+                        parse_reported = true;
+                    }
+                }
+                Ok(phase) => {
+                    emit(phase);
+                    return;
+                }
+                Err(err) => {
+                    emit(Phase::Parsed(Err(timeout_error(err, timeout))));
+                    return;
+                }
+            }
+
+            while let Some(&part) = parts.first() {
+                match receiver.recv_timeout(timeout) {
+                    Ok(phase) => {
+                        parts.remove(0);
+                        emit(phase);
+                    }
+                    Err(err) => {
+                        parts.remove(0);
+                        emit(Phase::Solved(part, Err(timeout_error(err, timeout))));
+                        break;
+                    }
+                }
+            }
+        }
+    }
+
+    /// Runs the selected parts of this day. Failures are recorded in the result rather than
+    /// returned, so a caller running several days can carry on with the rest.
+    fn run(
+        &self,
+        source: &InputSource,
+        options: &RunOptions,
+        reporter: &mut dyn Reporter,
+    ) -> DayResult
+    where
+        Self: 'static,
+    {
+        let (year, day) = (self.year(), self.day());
+        let mut result = DayResult {
+            year,
+            day,
+            input_bytes: 0,
+            parse_duration: None,
+            part1: None,
+            part2: None,
+            failures: Vec::new(),
+        };
+
         let start = Instant::now();
-        let input = fs::read_to_string(path)?;
-        println!("Day {:02}", day);
-        println!("====================");
-        println!(
-            "Reading input took: {:?}, read {} bytes",
-            start.elapsed(),
-            input.len()
-        );
-        Self::solve(&input)
+        let input = match source.read() {
+            Ok(input) => input,
+            Err(err) => {
+                let failure = Failure {
+                    year,
+                    day,
+                    part: None,
+                    message: format!("{:#}", err),
+                };
+                reporter.failed(&failure);
+                result.failures.push(failure);
+                reporter.day_finished(&result);
+                return result;
+            }
+        };
+        result.input_bytes = input.len();
+        reporter.input_read(year, day, source, input.len(), start.elapsed());
+
+        let parts: Vec<u8> = [1, 2]
+            .into_iter()
+            .filter(|&part| options.parts.includes(part))
+            .collect();
+        let mut record = |phase: Phase| {
+            let (part, err) = match phase {
+                Phase::Parsed(Ok(duration)) => {
+                    reporter.parsed(day, duration);
+                    result.parse_duration = Some(duration);
+                    return;
+                }
+                Phase::Solved(part, Ok(part_result)) => {
+                    reporter.part_solved(day, part, &part_result);
+                    match part {
+                        1 => result.part1 = Some(part_result),
+                        _ => result.part2 = Some(part_result),
+                    }
+                    return;
+                }
+                Phase::Parsed(Err(err)) => (None, err),
+                Phase::Solved(part, Err(err)) => (Some(part), err),
+            };
+            let failure = Failure {
+                year,
+                day,
+                part,
+                message: format!("{:#} (input: {})", err, source),
+            };
+            reporter.failed(&failure);
+            result.failures.push(failure);
+        };
+
+        match options.timeout {
+            Some(timeout) => {
+                Self::solve_phases_with_timeout(Arc::from(input), parts, timeout, &mut record)
+            }
+            None => Self::solve_phases(&input, &parts, &mut |phase| {
+                record(phase);
+                true
+            }),
+        }
+
+        reporter.day_finished(&result);
+        result
+    }
+
+    fn bench(&self, source: &InputSource, options: &RunOptions, runs: usize) -> Result<DayBench> {
+        let input = source.read()?;
+        let mut phases = vec![(
+            "Parse".to_string(),
+            bench::measure(runs, || Self::parse(&input).map(|_| ()))?,
+        )];
+
+        let parsed = Self::parse(&input)?;
+        for part in [1, 2]
+            .into_iter()
+            .filter(|&part| options.parts.includes(part))
+        {
+            let stats = bench::measure(runs, || Self::part(part, &parsed).map(|_| ()))?;
+            phases.push((format!("Part {}", part), stats));
+        }
+
+        Ok(DayBench {
+            day: self.day(),
+            phases,
+        })
+    }
+
+    /// Solves each selected part over and over for `budget`, recording the spans it opens.
+    ///
+    /// The input is parsed once up front, so a sampling profiler attached meanwhile only
+    /// sees the part itself.
+    fn profile(
+        &self,
+        source: &InputSource,
+        options: &RunOptions,
+        budget: Duration,
+    ) -> Result<DayProfile> {
+        let input = source.read()?;
+        let parsed = Self::parse(&input)?;
+
+        let mut parts = Vec::new();
+        for part in [1, 2]
+            .into_iter()
+            .filter(|&part| options.parts.includes(part))
+        {
+            profile::take_spans();
+            profile::set_enabled(true);
+            let repeated = profile::repeat_for(budget, || {
+                Self::part(part, &parsed).map(|answer| {
+                    std::hint::black_box(answer);
+                })
+            });
+            profile::set_enabled(false);
+            let (iterations, elapsed) = repeated?;
+            parts.push(PartProfile {
+                part,
+                iterations,
+                elapsed,
+                spans: profile::take_spans(),
+            });
+        }
+
+        Ok(DayProfile {
+            day: self.day(),
+            parts,
+        })
+    }
+
+    /// Records `part`'s animation into the sink `open` returns, which is only opened if the
+    /// part has an animation. Returns the number of steps recorded.
+    fn animate(
+        &self,
+        source: &InputSource,
+        part: u8,
+        open: &mut dyn FnMut() -> Result<Box<dyn FrameSink>>,
+    ) -> Result<Option<usize>> {
+        let input = source.read()?;
+        let parsed = Self::parse(&input)?;
+        let Some(mut animation) = Self::animation(part, &parsed) else {
+            return Ok(None);
+        };
+        animate::record(animation.as_mut(), open()?.as_mut()).map(Some)
     }
 
     #[cfg(test)]
     fn run_test1(&self) -> Self::Answer {
-        let day = self.day();
-        let path = format!("./src/input/sample{:02}.txt", day);
-        let input = fs::read_to_string(path).unwrap();
-        Self::part1(&input).expect("Part 1 failed")
+        let path = crate::input::sample_path(self.year(), self.day());
+        let input = std::fs::read_to_string(path).unwrap();
+        Self::solve(1, &input).expect("Part 1 failed")
     }
 
     #[cfg(test)]
     fn run_test2(&self) -> Self::Answer {
-        let day = self.day();
-        let path = format!("./src/input/sample{:02}.txt", day);
-        let input = fs::read_to_string(path).unwrap();
-        Self::part2(&input).expect("Part 2 failed")
+        let path = crate::input::sample_path(self.year(), self.day());
+        let input = std::fs::read_to_string(path).unwrap();
+        Self::solve(2, &input).expect("Part 2 failed")
+    }
+}
+
+type PanicHook = Box<dyn Fn(&panic::PanicHookInfo<'_>) + Sync + Send>;
+
+/// How many [`catch_panic`] calls are running, and the hook to put back once none are.
+static QUIET_PANICS: Mutex<(usize, Option<PanicHook>)> = Mutex::new((0, None));
+
+/// Runs `f`, turning a panic inside it into an error.
+///
+/// The panic hook is silenced meanwhile, since the panic is reported as a failure instead.
+/// Hooks are global, so this is counted: overlapping calls, from timed out parts still
+/// running or from tests, keep it silent until the last of them is done.
+fn catch_panic<T>(f: impl FnOnce() -> Result<T>) -> Result<T> {
+    {
+        let mut quiet = QUIET_PANICS.lock().unwrap_or_else(|e| e.into_inner());
+        if quiet.0 == 0 {
+            quiet.1 = Some(panic::take_hook());
+            panic::set_hook(Box::new(|_| {}));
+        }
+        quiet.0 += 1;
+    }
+
+    let result = panic::catch_unwind(AssertUnwindSafe(f));
+
+    {
+        let mut quiet = QUIET_PANICS.lock().unwrap_or_else(|e| e.into_inner());
+        quiet.0 -= 1;
+        if quiet.0 == 0 {
+            if let Some(hook) = quiet.1.take() {
+                panic::set_hook(hook);
+            }
+        }
+    }
+
+    result.unwrap_or_else(|payload| Err(anyhow!("panicked: {}", panic_message(&payload))))
+}
+
+fn timeout_error(err: RecvTimeoutError, timeout: Duration) -> anyhow::Error {
+    match err {
+        RecvTimeoutError::Timeout => anyhow!("timed out after {:?}", timeout),
+        RecvTimeoutError::Disconnected => anyhow!("worker thread exited early"),
+    }
+}
+
+fn panic_message(payload: &Box<dyn Any + Send>) -> &str {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message
+    } else {
+        "unknown panic payload"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, fs, path::PathBuf};
+
+    use super::*;
+
+    struct Panicky;
+
+    impl Solution for Panicky {
+        type Answer = u8;
+        type Parsed<'a> = &'a str;
+        fn day(&self) -> u8 {
+            99
+        }
+
+        fn parse(input: &str) -> Result<Self::Parsed<'_>> {
+            Ok(input)
+        }
+
+        fn part1(_: &&str) -> Result<Self::Answer> {
+            Ok(1)
+        }
+
+        fn part2(_: &&str) -> Result<Self::Answer> {
+            panic!("Invalid position")
+        }
+    }
+
+    struct Slow;
+
+    impl Solution for Slow {
+        type Answer = u8;
+        type Parsed<'a> = &'a str;
+        fn day(&self) -> u8 {
+            98
+        }
+
+        fn parse(input: &str) -> Result<Self::Parsed<'_>> {
+            Ok(input)
+        }
+
+        fn part1(_: &&str) -> Result<Self::Answer> {
+            thread::sleep(Duration::from_secs(5));
+            Ok(1)
+        }
+
+        fn part2(_: &&str) -> Result<Self::Answer> {
+            Ok(2)
+        }
+    }
+
+    /// Counts its lines once, then both parts read the count.
+    struct Lines;
+
+    impl Solution for Lines {
+        type Answer = usize;
+        type Parsed<'a> = Vec<&'a str>;
+        fn day(&self) -> u8 {
+            97
+        }
+
+        fn parse(input: &str) -> Result<Self::Parsed<'_>> {
+            if input.is_empty() {
+                return Err(anyhow!("Empty input"));
+            }
+            Ok(input.lines().collect())
+        }
+
+        fn part1(lines: &Vec<&str>) -> Result<Self::Answer> {
+            Ok(lines.len())
+        }
+
+        fn part2(lines: &Vec<&str>) -> Result<Self::Answer> {
+            Ok(lines.iter().map(|line| line.len()).sum())
+        }
+    }
+
+    struct Quiet;
+
+    impl Reporter for Quiet {}
+
+    #[test]
+    fn panics_become_failures_with_context() {
+        let source = InputSource::File(PathBuf::from("./src/input/sample01.txt"));
+        let result = Panicky.run(&source, &RunOptions::default(), &mut Quiet);
+
+        assert_eq!(result.part1.as_ref().unwrap().answer, "1");
+        assert!(result.part2.is_none());
+        assert_eq!(
+            result.failures,
+            vec![Failure {
+                year: 2024,
+                day: 99,
+                part: Some(2),
+                message: "panicked: Invalid position (input: ./src/input/sample01.txt)".into(),
+            }]
+        );
+    }
+
+    #[test]
+    fn slow_parts_time_out() {
+        let source = InputSource::File(PathBuf::from("./src/input/sample01.txt"));
+        let options = RunOptions {
+            timeout: Some(Duration::from_millis(100)),
+            ..Default::default()
+        };
+        let result = Slow.run(&source, &options, &mut Quiet);
+
+        assert!(result.part1.is_none());
+        assert_eq!(
+            result.failure(1).unwrap().message,
+            "timed out after 100ms (input: ./src/input/sample01.txt)"
+        );
+        // Part 2 still runs on a fresh worker, without reporting a second parse.
+        assert_eq!(result.part2.as_ref().unwrap().answer, "2");
+        assert!(result.parse_duration.is_some());
+        assert_eq!(result.failures.len(), 1);
+    }
+
+    #[test]
+    fn parses_once_for_both_parts() {
+        let source = InputSource::File(PathBuf::from("./src/input/sample01.txt"));
+        let result = Lines.run(&source, &RunOptions::default(), &mut Quiet);
+
+        assert!(result.parse_duration.is_some());
+        assert_eq!(result.part1.as_ref().unwrap().answer, "6");
+        assert_eq!(result.part2.as_ref().unwrap().answer, "30");
+        assert!(result.failures.is_empty());
+    }
+
+    #[test]
+    fn parse_errors_fail_the_whole_day() {
+        let empty = InputSource::File(
+            env::temp_dir().join(format!("aoc2024-empty-{}.txt", std::process::id())),
+        );
+        fs::write(empty.to_string(), "").unwrap();
+        let result = Lines.run(&empty, &RunOptions::default(), &mut Quiet);
+        fs::remove_file(empty.to_string()).unwrap();
+
+        assert!(result.parse_duration.is_none());
+        assert!(result.part1.is_none() && result.part2.is_none());
+        assert_eq!(result.failures.len(), 1);
+        assert_eq!(result.failures[0].part, None);
+        assert!(result.failures[0].message.starts_with("Empty input"));
+    }
+
+    #[test]
+    fn panics_are_caught_on_worker_threads() {
+        let source = InputSource::File(PathBuf::from("./src/input/sample01.txt"));
+        let options = RunOptions {
+            timeout: Some(Duration::from_secs(5)),
+            ..Default::default()
+        };
+        let result = Panicky.run(&source, &options, &mut Quiet);
+
+        assert_eq!(result.part1.as_ref().unwrap().answer, "1");
+        assert!(result.failure(2).unwrap().message.starts_with("panicked"));
+    }
+
+    #[test]
+    fn unreadable_input_fails_the_whole_day() {
+        let source = InputSource::File(PathBuf::from("./src/input/missing.txt"));
+        let result = Panicky.run(&source, &RunOptions::default(), &mut Quiet);
+
+        assert!(result.part1.is_none() && result.part2.is_none());
+        assert_eq!(result.failures.len(), 1);
+        assert_eq!(result.failures[0].part, None);
+        assert!(result.failure(1).is_some() && result.failure(2).is_some());
+    }
+
+    #[test]
+    fn parses_parts() {
+        assert_eq!("1".parse::<Parts>().unwrap(), Parts::One);
+        assert_eq!("2".parse::<Parts>().unwrap(), Parts::Two);
+        assert_eq!("both".parse::<Parts>().unwrap(), Parts::Both);
+        assert!("3".parse::<Parts>().is_err());
+    }
+
+    #[test]
+    fn parts_include_selected_part() {
+        assert!(Parts::One.includes(1) && !Parts::One.includes(2));
+        assert!(!Parts::Two.includes(1) && Parts::Two.includes(2));
+        assert!(Parts::Both.includes(1) && Parts::Both.includes(2));
     }
 }
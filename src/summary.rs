@@ -0,0 +1,226 @@
+use std::time::Duration;
+
+use crate::solution::DayResult;
+
+const HEADERS: [&str; 6] = ["Day", "Parse", "Part 1", "Time", "Part 2", "Time"];
+
+pub fn print_summary(results: &[DayResult]) {
+    print!("{}", render_summary(results));
+}
+
+fn render_summary(results: &[DayResult]) -> String {
+    let rows: Vec<[String; 6]> = results
+        .iter()
+        .map(|result| {
+            let [part1_answer, part1_time] = part_cells(result, 1);
+            let [part2_answer, part2_time] = part_cells(result, 2);
+            [
+                format!("{:02}", result.day),
+                result
+                    .parse_duration
+                    .map(format_duration)
+                    .unwrap_or_else(|| "-".to_string()),
+                part1_answer,
+                part1_time,
+                part2_answer,
+                part2_time,
+            ]
+        })
+        .collect();
+
+    let total = results
+        .iter()
+        .flat_map(|result| {
+            let parts = [&result.part1, &result.part2];
+            let part_durations = parts.into_iter().flatten().map(|part| part.duration);
+            result.parse_duration.into_iter().chain(part_durations)
+        })
+        .sum::<Duration>();
+
+    let mut widths = HEADERS.map(str::len);
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    let mut out = String::new();
+    out.push_str(&format_row(&HEADERS.map(String::from), &widths));
+    out.push_str(
+        &widths
+            .iter()
+            .map(|&width| "-".repeat(width))
+            .collect::<Vec<_>>()
+            .join("-+-"),
+    );
+    out.push('\n');
+    for row in &rows {
+        out.push_str(&format_row(row, &widths));
+    }
+    out.push_str(&format!(
+        "\n{} day(s), total solve time: {}\n",
+        results.len(),
+        format_duration(total)
+    ));
+
+    let failures: Vec<_> = results.iter().flat_map(|result| &result.failures).collect();
+    if !failures.is_empty() {
+        out.push_str(&format!("\n{} failure(s):\n", failures.len()));
+        for failure in failures {
+            out.push_str(&format!("  {}\n", failure));
+        }
+    }
+    out
+}
+
+fn part_cells(result: &DayResult, part: u8) -> [String; 2] {
+    match (result.part(part), result.failure(part)) {
+        (Some(part), _) => [part.answer.clone(), format_duration(part.duration)],
+        (None, Some(_)) => ["FAILED".to_string(), "-".to_string()],
+        (None, None) => ["-".to_string(), "-".to_string()],
+    }
+}
+
+fn format_row(row: &[String; 6], widths: &[usize; 6]) -> String {
+    let cells: Vec<_> = row
+        .iter()
+        .zip(widths)
+        .map(|(cell, &width)| format!("{:<width$}", cell, width = width))
+        .collect();
+    format!("{}\n", cells.join(" | ").trim_end())
+}
+
+fn format_duration(duration: Duration) -> String {
+    format!("{:.2?}", duration)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution::{Failure, PartResult};
+
+    #[test]
+    fn renders_aligned_table() {
+        let results = [
+            DayResult {
+                year: 2024,
+                day: 1,
+                input_bytes: 35,
+                parse_duration: Some(Duration::from_micros(1)),
+                part1: Some(PartResult {
+                    answer: "11".into(),
+                    duration: Duration::from_micros(5),
+                    memory: None,
+                }),
+                part2: Some(PartResult {
+                    answer: "31".into(),
+                    duration: Duration::from_micros(7),
+                    memory: None,
+                }),
+                failures: Vec::new(),
+            },
+            DayResult {
+                year: 2024,
+                day: 23,
+                input_bytes: 128,
+                parse_duration: Some(Duration::from_millis(1)),
+                part1: Some(PartResult {
+                    answer: "7".into(),
+                    duration: Duration::from_millis(2),
+                    memory: None,
+                }),
+                part2: Some(PartResult {
+                    answer: "co,de,ka,ta".into(),
+                    duration: Duration::from_millis(3),
+                    memory: None,
+                }),
+                failures: Vec::new(),
+            },
+        ];
+
+        let table = render_summary(&results);
+        let lines: Vec<_> = table.lines().collect();
+        assert_eq!(
+            lines[0],
+            "Day | Parse  | Part 1 | Time   | Part 2      | Time"
+        );
+        assert_eq!(
+            lines[2],
+            "01  | 1.00µs | 11     | 5.00µs | 31          | 7.00µs"
+        );
+        assert_eq!(
+            lines[3],
+            "23  | 1.00ms | 7      | 2.00ms | co,de,ka,ta | 3.00ms"
+        );
+        assert!(table.ends_with("2 day(s), total solve time: 6.01ms\n"));
+    }
+
+    #[test]
+    fn skipped_parts_render_as_dash() {
+        let results = [DayResult {
+            year: 2024,
+            day: 6,
+            input_bytes: 109,
+            parse_duration: None,
+            part1: Some(PartResult {
+                answer: "41".into(),
+                duration: Duration::from_micros(3),
+                memory: None,
+            }),
+            part2: None,
+            failures: Vec::new(),
+        }];
+
+        let table = render_summary(&results);
+        assert_eq!(
+            table.lines().nth(2).unwrap(),
+            "06  | -     | 41     | 3.00µs | -      | -"
+        );
+    }
+
+    #[test]
+    fn failures_are_listed_after_the_table() {
+        let results = [
+            DayResult {
+                year: 2024,
+                day: 15,
+                input_bytes: 10,
+                parse_duration: None,
+                part1: Some(PartResult {
+                    answer: "10092".into(),
+                    duration: Duration::from_micros(3),
+                    memory: None,
+                }),
+                part2: None,
+                failures: vec![Failure {
+                    year: 2024,
+                    day: 15,
+                    part: Some(2),
+                    message: "panicked: Invalid position (input: day15.txt)".into(),
+                }],
+            },
+            DayResult {
+                year: 2024,
+                day: 16,
+                input_bytes: 0,
+                parse_duration: None,
+                part1: None,
+                part2: None,
+                failures: vec![Failure {
+                    year: 2024,
+                    day: 16,
+                    part: None,
+                    message: "Failed to read input from day16.txt".into(),
+                }],
+            },
+        ];
+
+        let table = render_summary(&results);
+        let lines: Vec<_> = table.lines().collect();
+        assert_eq!(lines[2], "15  | -     | 10092  | 3.00µs | FAILED | -");
+        assert_eq!(lines[3], "16  | -     | FAILED | -      | FAILED | -");
+        assert!(table.ends_with(
+            "2 failure(s):\n  Day 15 part 2: panicked: Invalid position (input: day15.txt)\n  Day 16: Failed to read input from day16.txt\n"
+        ));
+    }
+}
@@ -0,0 +1,69 @@
+use anyhow::{Context, Result};
+use rayon::ThreadPoolBuilder;
+
+/// Sizes rayon's global pool, which every `par_iter` in the days runs on. `1` makes them sequential.
+///
+/// Has to be called before anything uses the pool, it can only be set up once.
+pub fn configure_global(threads: usize) -> Result<()> {
+    ThreadPoolBuilder::new()
+        .num_threads(threads)
+        .build_global()
+        .context("Failed to configure the thread pool")
+}
+
+/// Runs `f` with its parallel iterators on a dedicated pool of `threads` threads.
+pub fn with_threads<T, F>(threads: usize, f: F) -> Result<T>
+where
+    T: Send,
+    F: FnOnce() -> T + Send,
+{
+    let pool = ThreadPoolBuilder::new()
+        .num_threads(threads)
+        .build()
+        .context("Failed to build a thread pool")?;
+    Ok(pool.install(f))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::*;
+    use crate::{
+        days::{Day06, Day07, Day19, Day20, Day22},
+        input,
+        solution::Solution,
+    };
+
+    fn assert_sequential_matches_parallel<S: Solution>(solution: S) {
+        let path = input::sample_path(solution.year(), solution.day());
+        let sample = fs::read_to_string(path).unwrap();
+        for part in [1, 2] {
+            let solve = || S::solve(part, &sample).map(|answer| answer.to_string());
+            let sequential = with_threads(1, solve).unwrap().unwrap();
+            let parallel = with_threads(4, solve).unwrap().unwrap();
+            assert_eq!(
+                sequential,
+                parallel,
+                "day {:02} part {}",
+                solution.day(),
+                part
+            );
+        }
+    }
+
+    #[test]
+    fn pools_have_the_requested_size() {
+        assert_eq!(with_threads(1, rayon::current_num_threads).unwrap(), 1);
+        assert_eq!(with_threads(3, rayon::current_num_threads).unwrap(), 3);
+    }
+
+    #[test]
+    fn parallel_days_match_sequential_runs() {
+        assert_sequential_matches_parallel(Day06);
+        assert_sequential_matches_parallel(Day07);
+        assert_sequential_matches_parallel(Day19);
+        assert_sequential_matches_parallel(Day20);
+        assert_sequential_matches_parallel(Day22);
+    }
+}
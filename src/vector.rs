@@ -4,6 +4,8 @@ use std::{
     str::FromStr,
 };
 
+use crate::parse::Text;
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct Vec2 {
     pub x: i64,
@@ -66,6 +68,19 @@ impl Vec2 {
             *self + Self::RIGHT,
         ]
     }
+
+    pub fn get_surrounding_8(&self) -> [Self; 8] {
+        [
+            *self + Self::UP + Self::LEFT,
+            *self + Self::UP,
+            *self + Self::UP + Self::RIGHT,
+            *self + Self::LEFT,
+            *self + Self::RIGHT,
+            *self + Self::DOWN + Self::LEFT,
+            *self + Self::DOWN,
+            *self + Self::DOWN + Self::RIGHT,
+        ]
+    }
 }
 
 impl Add for Vec2 {
@@ -140,10 +155,11 @@ impl FromStr for Vec2 {
     type Err = anyhow::Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (x, y) = s.split_once(',').unwrap();
+        let text = Text::new(s);
+        let (x, y) = text.split_once(s, ",")?;
         Ok(Self {
-            x: x.parse()?,
-            y: y.parse()?,
+            x: text.parse(x)?,
+            y: text.parse(y)?,
         })
     }
 }
@@ -1,13 +1,21 @@
 use std::{
+    collections::HashMap,
     fmt::{Display, Formatter},
+    iter::Enumerate,
+    ops::{Index, IndexMut},
+    slice,
     str::FromStr,
 };
 
-use crate::vector::Vec2;
+use crate::{
+    parse::{ParseError, Text},
+    vector::Vec2,
+};
 
+/// A rectangular grid, stored row by row in one contiguous `Vec`.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct VectorMap<T> {
-    map: Vec<Vec<T>>,
+    cells: Vec<T>,
     size: Vec2,
 }
 
@@ -16,52 +24,200 @@ impl<T> VectorMap<T> {
     where
         T: Clone,
     {
-        let map = vec![vec![default; size.x as usize]; size.y as usize];
-        VectorMap { map, size }
+        let cells = vec![default; (size.x * size.y) as usize];
+        VectorMap { cells, size }
     }
-    pub fn get(&self, pos: &Vec2) -> Option<&T> {
+
+    /// Where `pos` is stored in `cells`, if it is on the map.
+    fn index_of(&self, pos: &Vec2) -> Option<usize> {
         if pos.contained_in(Vec2::new(0, 0), self.size) {
-            Some(&self.map[pos.y as usize][pos.x as usize])
+            Some((pos.y * self.size.x + pos.x) as usize)
         } else {
             None
         }
     }
 
+    fn position_of(&self, index: usize) -> Vec2 {
+        let width = self.size.x as usize;
+        (index % width, index / width).into()
+    }
+
+    pub fn get(&self, pos: &Vec2) -> Option<&T> {
+        self.index_of(pos).map(|index| &self.cells[index])
+    }
+
+    pub fn get_mut(&mut self, pos: &Vec2) -> Option<&mut T> {
+        self.index_of(pos).map(|index| &mut self.cells[index])
+    }
+
+    /// Sets the value at `pos`, panicking if it is off the map.
     pub fn set(&mut self, pos: &Vec2, value: T) {
-        self.map[pos.y as usize][pos.x as usize] = value;
+        self[*pos] = value;
     }
 
-    pub fn iter(&self) -> VectorMapIterator<T> {
+    /// Sets the value at `pos` if it is on the map, returning whether it was.
+    pub fn try_set(&mut self, pos: &Vec2, value: T) -> bool {
+        match self.get_mut(pos) {
+            Some(cell) => {
+                *cell = value;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Every position and its value, row by row.
+    pub fn iter(&self) -> VectorMapIterator<'_, T> {
         VectorMapIterator {
-            map: self,
-            curr_pos: Vec2::new(0, 0),
+            cells: self.cells.iter().enumerate(),
+            width: self.size.x as usize,
+        }
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks_exact(self.size.x.max(1) as usize)
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        let width = self.size.x as usize;
+        (0..width).map(move |x| self.cells[x..].iter().step_by(width))
+    }
+
+    /// The orthogonal neighbors of `pos` that are on the map.
+    pub fn neighbors4(&self, pos: Vec2) -> impl Iterator<Item = (Vec2, &T)> {
+        pos.get_surrounding_4()
+            .into_iter()
+            .filter_map(|neighbor| self.get(&neighbor).map(|value| (neighbor, value)))
+    }
+
+    /// The orthogonal and diagonal neighbors of `pos` that are on the map.
+    pub fn neighbors8(&self, pos: Vec2) -> impl Iterator<Item = (Vec2, &T)> {
+        pos.get_surrounding_8()
+            .into_iter()
+            .filter_map(|neighbor| self.get(&neighbor).map(|value| (neighbor, value)))
+    }
+
+    /// The first position, row by row, whose value matches `predicate`.
+    pub fn find(&self, predicate: impl FnMut(&T) -> bool) -> Option<Vec2> {
+        self.cells
+            .iter()
+            .position(predicate)
+            .map(|index| self.position_of(index))
+    }
+
+    /// A map of the same size with `f` applied to every value.
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> VectorMap<U> {
+        VectorMap {
+            cells: self.cells.iter().map(f).collect(),
+            size: self.size,
         }
     }
 
     pub fn size(&self) -> Vec2 {
         self.size
     }
+
+    /// Parses a rectangular grid of characters, turning each one into a value with `f`.
+    ///
+    /// The positions of every character in `markers` are collected on the way, for grids
+    /// that mark a start or an end on an otherwise plain tile. Ragged rows and errors from
+    /// `f` are reported at the offending character.
+    pub fn parse_with<'a>(
+        s: &'a str,
+        markers: &str,
+        mut f: impl FnMut(char, Vec2) -> anyhow::Result<T>,
+    ) -> Result<(Self, Markers<'a>), ParseError> {
+        let text = Text::new(s);
+        let rows = text.grid()?;
+        let width = rows[0].chars().count();
+        let size = (width, rows.len()).into();
+
+        let mut positions: HashMap<char, Vec<Vec2>> =
+            markers.chars().map(|marker| (marker, Vec::new())).collect();
+        let mut cells = Vec::with_capacity(width * rows.len());
+        for (y, row) in rows.iter().enumerate() {
+            for (x, c) in row.chars().enumerate() {
+                let pos = (x, y).into();
+                if let Some(found) = positions.get_mut(&c) {
+                    found.push(pos);
+                }
+                let value =
+                    f(c, pos).map_err(|error| text.char_error(row, x, error.to_string()))?;
+                cells.push(value);
+            }
+        }
+
+        let markers = Markers {
+            text,
+            rows,
+            positions,
+        };
+        Ok((VectorMap { cells, size }, markers))
+    }
+}
+
+/// Where the marker characters of a grid read by [`VectorMap::parse_with`] are.
+#[derive(Debug)]
+pub struct Markers<'a> {
+    text: Text<'a>,
+    rows: Vec<&'a str>,
+    positions: HashMap<char, Vec<Vec2>>,
+}
+
+impl Markers<'_> {
+    /// Every position of `marker`, row by row.
+    pub fn all(&self, marker: char) -> &[Vec2] {
+        self.positions.get(&marker).map_or(&[], Vec::as_slice)
+    }
+
+    /// The position of `marker`, which has to appear exactly once.
+    pub fn one(&self, marker: char) -> Result<Vec2, ParseError> {
+        match self.all(marker) {
+            [position] => Ok(*position),
+            [] => Err(self
+                .text
+                .error(self.text.as_str(), format!("no `{}` on the map", marker))),
+            [_, second, ..] => Err(self.text.char_error(
+                self.rows[second.y as usize],
+                second.x as usize,
+                format!("more than one `{}` on the map", marker),
+            )),
+        }
+    }
+}
+
+impl<T> Index<Vec2> for VectorMap<T> {
+    type Output = T;
+
+    fn index(&self, pos: Vec2) -> &T {
+        match self.index_of(&pos) {
+            Some(index) => &self.cells[index],
+            None => panic!("{} is off a map of size {}", pos, self.size),
+        }
+    }
+}
+
+impl<T> IndexMut<Vec2> for VectorMap<T> {
+    fn index_mut(&mut self, pos: Vec2) -> &mut T {
+        match self.index_of(&pos) {
+            Some(index) => &mut self.cells[index],
+            None => panic!("{} is off a map of size {}", pos, self.size),
+        }
+    }
 }
 
 impl FromStr for VectorMap<char> {
     type Err = anyhow::Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let map: Vec<Vec<_>> = s.lines().map(|line| line.chars().collect()).collect();
-
-        let width = map[0].len();
-        let height = map.len();
-
-        Ok(VectorMap {
-            size: (width, height).into(),
-            map,
-        })
+        let (map, _) = VectorMap::parse_with(s, "", |c, _| Ok(c))?;
+        Ok(map)
     }
 }
 
 impl Display for VectorMap<char> {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        for row in &self.map {
+        for row in self.rows() {
             for &c in row {
                 write!(f, "{}", c)?;
             }
@@ -72,21 +228,121 @@ impl Display for VectorMap<char> {
 }
 
 pub struct VectorMapIterator<'a, T> {
-    map: &'a VectorMap<T>,
-    curr_pos: Vec2,
+    cells: Enumerate<slice::Iter<'a, T>>,
+    width: usize,
 }
 
 impl<'a, T> Iterator for VectorMapIterator<'a, T> {
     type Item = (Vec2, &'a T);
 
     fn next(&mut self) -> Option<Self::Item> {
-        let pos = self.curr_pos;
-        self.curr_pos.x += 1;
-        if self.curr_pos.x == self.map.size.x {
-            self.curr_pos.x = 0;
-            self.curr_pos.y += 1;
-        }
+        let (index, value) = self.cells.next()?;
+        Some(((index % self.width, index / self.width).into(), value))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.cells.size_hint()
+    }
+}
+
+impl<T> ExactSizeIterator for VectorMapIterator<'_, T> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> VectorMap<char> {
+        "abc\ndef".parse().unwrap()
+    }
+
+    #[test]
+    fn gets_and_sets() {
+        let mut map = sample();
+        assert_eq!(map.size(), Vec2::new(3, 2));
+        assert_eq!(map.get(&Vec2::new(2, 1)), Some(&'f'));
+        assert_eq!(map.get(&Vec2::new(3, 0)), None);
+        assert_eq!(map.get(&Vec2::new(0, -1)), None);
+
+        map.set(&Vec2::new(0, 1), 'x');
+        *map.get_mut(&Vec2::new(1, 0)).unwrap() = 'y';
+        map[Vec2::new(2, 0)] = 'z';
+        assert!(map.try_set(&Vec2::new(2, 1), 'w'));
+        assert!(!map.try_set(&Vec2::new(-1, 0), 'v'));
+        assert_eq!(map.to_string(), "ayz\nxew\n");
+        assert_eq!(map[Vec2::new(1, 1)], 'e');
+    }
+
+    #[test]
+    #[should_panic(expected = "off a map")]
+    fn set_panics_off_the_map() {
+        // Out of range in x only, which a flat index would silently wrap into the next row.
+        sample().set(&Vec2::new(3, 0), 'x');
+    }
+
+    #[test]
+    fn rows_and_columns() {
+        let map = sample();
+        let rows: Vec<String> = map.rows().map(|row| row.iter().collect()).collect();
+        assert_eq!(rows, vec!["abc", "def"]);
+        let columns: Vec<String> = map.columns().map(|column| column.collect()).collect();
+        assert_eq!(columns, vec!["ad", "be", "cf"]);
+
+        let positions: Vec<_> = map.iter().map(|(pos, _)| pos).collect();
+        assert_eq!(positions.len(), 6);
+        assert_eq!(positions[4], Vec2::new(1, 1));
+    }
+
+    #[test]
+    fn neighbors_stay_on_the_map() {
+        let map = sample();
+        let mut corner: Vec<_> = map.neighbors4(Vec2::new(0, 0)).map(|(_, &c)| c).collect();
+        corner.sort();
+        assert_eq!(corner, vec!['b', 'd']);
+        assert_eq!(map.neighbors8(Vec2::new(0, 0)).count(), 3);
+        assert_eq!(map.neighbors8(Vec2::new(1, 0)).count(), 5);
+    }
+
+    #[test]
+    fn find_and_map() {
+        let map = sample();
+        assert_eq!(map.find(|&c| c == 'e'), Some(Vec2::new(1, 1)));
+        assert_eq!(map.find(|&c| c == 'q'), None);
+
+        let upper = map.map(|c| c.to_ascii_uppercase());
+        assert_eq!(upper.to_string(), "ABC\nDEF\n");
+    }
+
+    #[test]
+    fn parses_other_grids() {
+        let (map, markers) = VectorMap::parse_with("#S.\n.#E\n", "SE", |c, _| match c {
+            '#' => Ok(true),
+            '.' | 'S' | 'E' => Ok(false),
+            _ => Err(anyhow::anyhow!("expected a tile")),
+        })
+        .unwrap();
+        assert_eq!(map.size(), Vec2::new(3, 2));
+        assert_eq!(map.find(|&wall| !wall), Some(Vec2::new(1, 0)));
+        assert_eq!(markers.one('S').unwrap(), Vec2::new(1, 0));
+        assert_eq!(markers.all('E'), &[Vec2::new(2, 1)]);
+        assert!(markers.all('x').is_empty());
+    }
+
+    #[test]
+    fn parse_errors_point_at_the_character() {
+        let digits = |c: char, _| c.to_digit(10).ok_or(anyhow::anyhow!("expected a digit"));
+
+        let error = VectorMap::parse_with("123\n4x6", "", digits).unwrap_err();
+        assert_eq!((error.line, error.column), (2, 2));
+        assert_eq!(error.message, "expected a digit");
+
+        let error = VectorMap::parse_with("123\n45", "", digits).unwrap_err();
+        assert_eq!((error.line, error.column), (2, 1));
 
-        self.map.get(&pos).map(|v| (pos, v))
+        let (_, markers) = VectorMap::parse_with("101\n202", "12", digits).unwrap();
+        assert_eq!(markers.all('1').len(), 2);
+        let error = markers.one('1').unwrap_err();
+        assert_eq!((error.line, error.column), (1, 3));
+        assert_eq!(error.message, "more than one `1` on the map");
+        assert!(markers.one('3').is_err());
     }
 }
@@ -0,0 +1,253 @@
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+    thread,
+    time::{Duration, SystemTime},
+};
+
+use crate::{
+    input::InputSource,
+    registry::DynSolution,
+    report::Reporter,
+    solution::{DayResult, RunOptions},
+};
+
+/// Polls files for modification and reports which ones changed since the last check.
+pub struct Watcher {
+    files: Vec<(PathBuf, Option<SystemTime>)>,
+}
+
+impl Watcher {
+    pub fn new(paths: Vec<PathBuf>) -> Self {
+        let files = paths
+            .into_iter()
+            .map(|path| {
+                let modified = modified(&path);
+                (path, modified)
+            })
+            .collect();
+        Watcher { files }
+    }
+
+    pub fn changed(&mut self) -> Vec<PathBuf> {
+        let mut changed = Vec::new();
+        for (path, last_modified) in &mut self.files {
+            let modified = modified(path);
+            if modified != *last_modified {
+                *last_modified = modified;
+                changed.push(path.clone());
+            }
+        }
+        changed
+    }
+}
+
+fn modified(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|meta| meta.modified()).ok()
+}
+
+struct Silent;
+
+impl Reporter for Silent {}
+
+/// Runs `solution` on every file, then again on each file whenever it changes. Never returns.
+pub fn watch(
+    solution: &dyn DynSolution,
+    files: Vec<PathBuf>,
+    options: &RunOptions,
+    interval: Duration,
+) -> ! {
+    let mut previous: HashMap<PathBuf, DayResult> = HashMap::new();
+    let mut run = |path: &PathBuf| {
+        let result = solution.run(&InputSource::File(path.clone()), options, &mut Silent);
+        print!(
+            "{}",
+            render_run(&path.display().to_string(), previous.get(path), &result)
+        );
+        previous.insert(path.clone(), result);
+    };
+
+    println!(
+        "Watching {} file(s) for day {:02}, press Ctrl-C to stop",
+        files.len(),
+        solution.day()
+    );
+    for path in &files {
+        run(path);
+    }
+
+    let mut watcher = Watcher::new(files);
+    loop {
+        thread::sleep(interval);
+        for path in watcher.changed() {
+            run(&path);
+        }
+    }
+}
+
+fn render_run(name: &str, previous: Option<&DayResult>, result: &DayResult) -> String {
+    let mut out = format!("{}\n", name);
+    for failure in result
+        .failures
+        .iter()
+        .filter(|failure| failure.part.is_none())
+    {
+        out.push_str(&format!("  Failed: {}\n", failure.message));
+    }
+    if let Some(parse) = result.parse_duration {
+        out.push_str(&format!("  Parse: took {:.2?}", parse));
+        if let Some(previous_parse) = previous.and_then(|previous| previous.parse_duration) {
+            out.push_str(&format!(" ({})", format_change(previous_parse, parse)));
+        }
+        out.push('\n');
+    }
+
+    for part in [1, 2] {
+        let previous_part = previous.and_then(|previous| previous.part(part));
+        // Failures of the whole day were shown above already.
+        if let Some(failure) = result
+            .failures
+            .iter()
+            .find(|failure| failure.part == Some(part))
+        {
+            out.push_str(&format!("  Part {}: failed: {}\n", part, failure.message));
+            continue;
+        }
+        let Some(current) = result.part(part) else {
+            continue;
+        };
+
+        out.push_str(&format!("  Part {}: {}", part, current.answer));
+        if let Some(previous_part) = previous_part {
+            if previous_part.answer != current.answer {
+                out.push_str(&format!(" (was {})", previous_part.answer));
+            }
+        }
+        out.push_str(&format!(", took {:.2?}", current.duration));
+        if let Some(previous_part) = previous_part {
+            out.push_str(&format!(
+                " ({})",
+                format_change(previous_part.duration, current.duration)
+            ));
+        }
+        out.push('\n');
+    }
+    out
+}
+
+fn format_change(before: Duration, after: Duration) -> String {
+    if after >= before {
+        format!("+{:.2?}", after - before)
+    } else {
+        format!("-{:.2?}", before - after)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+
+    use super::*;
+    use crate::solution::{Failure, PartResult};
+
+    fn result(part1: &str, micros: u64) -> DayResult {
+        DayResult {
+            year: 2024,
+            day: 3,
+            input_bytes: 0,
+            parse_duration: None,
+            part1: Some(PartResult {
+                answer: part1.into(),
+                duration: Duration::from_micros(micros),
+                memory: None,
+            }),
+            part2: None,
+            failures: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn first_run_has_no_diff() {
+        assert_eq!(
+            render_run("sample03.txt", None, &result("161", 5)),
+            "sample03.txt\n  Part 1: 161, took 5.00µs\n"
+        );
+    }
+
+    #[test]
+    fn reruns_diff_answers_and_timings() {
+        let before = result("161", 5);
+        assert_eq!(
+            render_run("sample03.txt", Some(&before), &result("161", 3)),
+            "sample03.txt\n  Part 1: 161, took 3.00µs (-2.00µs)\n"
+        );
+        assert_eq!(
+            render_run("sample03.txt", Some(&before), &result("170", 8)),
+            "sample03.txt\n  Part 1: 170 (was 161), took 8.00µs (+3.00µs)\n"
+        );
+    }
+
+    #[test]
+    fn parse_time_is_diffed() {
+        let mut before = result("161", 5);
+        before.parse_duration = Some(Duration::from_micros(10));
+        let mut after = result("161", 5);
+        after.parse_duration = Some(Duration::from_micros(4));
+        assert_eq!(
+            render_run("day03.txt", Some(&before), &after),
+            "day03.txt\n  Parse: took 4.00µs (-6.00µs)\n  Part 1: 161, took 5.00µs (+0.00ns)\n"
+        );
+    }
+
+    #[test]
+    fn failures_are_shown() {
+        let mut failed = result("161", 5);
+        failed.part1 = None;
+        failed.failures.push(Failure {
+            year: 2024,
+            day: 3,
+            part: Some(1),
+            message: "panicked: boom".into(),
+        });
+        assert_eq!(
+            render_run("day03.txt", None, &failed),
+            "day03.txt\n  Part 1: failed: panicked: boom\n"
+        );
+    }
+
+    #[test]
+    fn day_failures_are_shown_once() {
+        let mut failed = result("161", 5);
+        failed.part1 = None;
+        failed.failures.push(Failure {
+            year: 2024,
+            day: 3,
+            part: None,
+            message: "line 1, column 1: expected a number".into(),
+        });
+        assert_eq!(
+            render_run("day03.txt", None, &failed),
+            "day03.txt\n  Failed: line 1, column 1: expected a number\n"
+        );
+    }
+
+    #[test]
+    fn detects_modified_files() {
+        let path = env::temp_dir().join(format!("aoc2024-watch-{}.txt", std::process::id()));
+        fs::write(&path, "1").unwrap();
+        let mut watcher = Watcher::new(vec![path.clone()]);
+        assert!(watcher.changed().is_empty());
+
+        let earlier = SystemTime::now() - Duration::from_secs(60);
+        fs::File::options()
+            .write(true)
+            .open(&path)
+            .unwrap()
+            .set_modified(earlier)
+            .unwrap();
+        assert_eq!(watcher.changed(), vec![path.clone()]);
+        assert!(watcher.changed().is_empty());
+        fs::remove_file(path).unwrap();
+    }
+}
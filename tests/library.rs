@@ -0,0 +1,36 @@
+use aoc2024::{
+    days::{day16::Maze, day18::MemoryMaze, Day01},
+    search,
+    solution::Solution,
+    vector::Vec2,
+    vector_map::VectorMap,
+};
+
+#[test]
+fn grid_utilities_are_public() {
+    let map: VectorMap<char> = "ab\ncd".parse().unwrap();
+    assert_eq!(map.size(), Vec2::new(2, 2));
+    assert_eq!(map.get(&Vec2::new(1, 1)), Some(&'d'));
+}
+
+#[test]
+fn solutions_are_public() {
+    assert_eq!(Day01::solve(1, "3   4\n4   6").unwrap(), 3);
+    let parsed = Day01::parse("3   4\n4   6").unwrap();
+    assert_eq!(Day01::part1(&parsed).unwrap(), 3);
+    assert_eq!(aoc2024::registry().days(2024).len(), 25);
+}
+
+#[test]
+fn pathfinders_are_public() {
+    let maze: Maze = "#####\n#S.E#\n#####".parse().unwrap();
+    let paths = maze.find_shortest_path();
+    assert_eq!(paths.cost(), Some(2));
+    assert_eq!(paths.path().unwrap().states.len(), 3);
+
+    let memory: MemoryMaze = "3,3\n\n1,0\n1,1".parse().unwrap();
+    assert_eq!(memory.to_vector_map(2).find_shortest_path(), Some(4));
+
+    let path = search::dijkstra([0u32], |&n| [(n + 1, 1u32), (n * 2, 1)], |&n| n == 10).unwrap();
+    assert_eq!(path.states, vec![0, 1, 2, 4, 5, 10]);
+}